@@ -1,5 +1,6 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
+use cascade_thugpro as thugpro;
 use iced::{
     event,
     keyboard::{self, key},
@@ -8,6 +9,7 @@ use iced::{
 };
 
 use crate::{
+    backups,
     config::{Config, Format, Selections},
     dashboard, paths, tasks, Element, Theme,
 };
@@ -15,6 +17,7 @@ use crate::{
 #[derive(Debug, Clone)]
 pub enum Message {
     Dashboard(dashboard::Message),
+    Backups(backups::Message),
 
     WroteConfig(Result<usize, tasks::Error>),
     WroteSelections(Result<usize, tasks::Error>),
@@ -30,22 +33,41 @@ pub struct Cascade {
     debug: bool,
     theme: Theme,
 
+    screen: Screen,
     dashboard: dashboard::Dashboard,
+    backups: backups::Backups,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Screen {
+    Dashboard,
+    Backups,
 }
 
 impl Cascade {
-    pub fn new(flags: (PathBuf, Config, Selections, Theme, bool)) -> (Self, Task<Message>) {
-        let (cascade_dir, config, selections, theme, debug) = flags;
+    pub fn new(
+        flags: (
+            PathBuf,
+            Config,
+            Selections,
+            Theme,
+            Arc<thugpro::Groups>,
+            bool,
+        ),
+    ) -> (Self, Task<Message>) {
+        let (cascade_dir, config, selections, theme, groups, debug) = flags;
         let backup_dir = paths::backup_dir(&cascade_dir);
 
+        let backups = backups::Backups::new(backup_dir.clone());
+
         let (dashboard, dashboard_command) = dashboard::Dashboard::new(
             config.source_path.clone(),
             config.saves_dir.clone(),
             backup_dir,
             config.default_selection,
             selections,
-            config.trickset,
-            config.scales,
+            groups,
+            config.components.clone(),
         );
 
         let config_path = paths::config(&cascade_dir);
@@ -58,7 +80,9 @@ impl Cascade {
                 theme,
                 config,
                 debug,
+                screen: Screen::Dashboard,
                 dashboard,
+                backups,
             },
             dashboard_command.map(Message::Dashboard),
         )
@@ -128,20 +152,44 @@ impl Cascade {
                             self.write_config()
                         }
 
-                        Some(dashboard::Event::SetTrickset(value)) => {
-                            self.config.trickset = value;
+                        Some(dashboard::Event::SetComponents(components)) => {
+                            self.config.components = components;
                             self.write_config()
                         }
 
-                        Some(dashboard::Event::SetScales(value)) => {
-                            self.config.scales = value;
-                            self.write_config()
+                        Some(dashboard::Event::OpenBackups) => {
+                            self.screen = Screen::Backups;
+                            self.backups
+                                .open(self.config.saves_dir.clone())
+                                .map(Message::Backups)
                         }
 
                         None => Task::none(),
                     },
                 ])
             }
+            Message::Backups(message) => {
+                let (command, event) = self.backups.update(message);
+
+                Task::batch(vec![
+                    command.map(Message::Backups),
+                    match event {
+                        Some(backups::Event::Close) => {
+                            self.screen = Screen::Dashboard;
+
+                            // Restored saves have changed on disk since they were loaded
+                            match self.config.saves_dir.clone() {
+                                Some(saves_dir) => self
+                                    .dashboard
+                                    .set_saves_dir(saves_dir)
+                                    .map(Message::Dashboard),
+                                None => Task::none(),
+                            }
+                        }
+                        None => Task::none(),
+                    },
+                ])
+            }
             Message::WroteConfig(Ok(_)) | Message::WroteSelections(Ok(_)) => Task::none(),
             Message::WroteConfig(Err(err)) => {
                 log::info!("error writing config: {:?}", err);
@@ -185,7 +233,12 @@ impl Cascade {
     }
 
     pub fn view(&self) -> Element<Message> {
-        let content: Element<Message> = container(self.dashboard.view().map(Message::Dashboard))
+        let screen = match self.screen {
+            Screen::Dashboard => self.dashboard.view().map(Message::Dashboard),
+            Screen::Backups => self.backups.view().map(Message::Backups),
+        };
+
+        let content: Element<Message> = container(screen)
             .width(Length::Fill)
             .height(Length::Fill)
             .padding(Padding::new(20.))
@@ -0,0 +1,455 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    result,
+};
+
+use cascade_thugpro as thugpro;
+use iced::{
+    alignment::Vertical,
+    font::Weight,
+    widget::{button, container, scrollable, text},
+    Font, Length, Task,
+};
+use indexmap::IndexMap;
+use tokio::fs;
+
+use crate::{
+    fonts,
+    manifest::{self, Manifest, Outcome},
+    paths, theme,
+    widget::{self, heading},
+    Column, Element, Row,
+};
+
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(io::ErrorKind),
+
+    #[error("thug pro error: {0}")]
+    ThugPro(#[from] thugpro::Error),
+
+    #[error("paths error: {0}")]
+    Path(#[from] paths::Error),
+
+    #[error("no saves dir set")]
+    NoSavesDir,
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value.kind())
+    }
+}
+
+pub type Result<T, E = Error> = result::Result<T, E>;
+
+/// A backup dir made by one cascade run.
+#[derive(Debug, Clone)]
+pub struct Run {
+    /// When the run happened, as the dir is named.
+    pub name: String,
+    pub entries: Vec<thugpro::Entry>,
+    /// Runs from before manifests were written, and restores, have none. Neither do runs whose
+    /// manifest could not be read.
+    pub manifest: Option<Manifest>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Refresh,
+    LoadedRuns(Result<Vec<Run>>),
+
+    SelectRun(usize),
+    ToggleSelectAll,
+    ToggleSelection(thugpro::Entry),
+
+    Restore,
+    Restored(Result<usize>),
+
+    Close,
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Close,
+}
+
+pub struct Backups {
+    backup_dir: PathBuf,
+    saves_dir: Option<PathBuf>,
+    enabled: bool,
+
+    runs: Vec<Run>,
+    run: Option<usize>,
+    selection: IndexMap<thugpro::Entry, bool>,
+
+    message: Option<String>,
+}
+
+impl Backups {
+    pub fn new(backup_dir: PathBuf) -> Self {
+        Self {
+            backup_dir,
+            saves_dir: None,
+            enabled: true,
+            runs: vec![],
+            run: None,
+            selection: IndexMap::new(),
+            message: None,
+        }
+    }
+
+    /// Reloads the list of runs, restoring into `saves_dir`.
+    pub fn open(&mut self, saves_dir: Option<PathBuf>) -> Task<Message> {
+        self.saves_dir = saves_dir;
+        self.message = None;
+
+        Task::perform(load_runs(self.backup_dir.clone()), Message::LoadedRuns)
+    }
+
+    fn notify(&mut self, msg: impl Into<String>) {
+        let msg = msg.into();
+        log::warn!("{}", msg);
+        self.message = Some(msg);
+    }
+
+    fn select_run(&mut self, index: Option<usize>) {
+        self.run = index.filter(|index| *index < self.runs.len());
+        self.selection = self
+            .run
+            .map(|index| {
+                self.runs[index]
+                    .entries
+                    .iter()
+                    .map(|entry| (entry.clone(), true))
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    pub fn update(&mut self, message: Message) -> (Task<Message>, Option<Event>) {
+        match message {
+            Message::Refresh => (self.open(self.saves_dir.clone()), None),
+            Message::LoadedRuns(Ok(runs)) => {
+                self.runs = runs;
+                self.select_run(None);
+                (Task::none(), None)
+            }
+            Message::LoadedRuns(Err(err)) => {
+                self.notify(format!("error loading backups: {}", err));
+                (Task::none(), None)
+            }
+
+            Message::SelectRun(index) => {
+                self.select_run(Some(index));
+                (Task::none(), None)
+            }
+            Message::ToggleSelectAll => {
+                let selected = !self.selection.values().all(|selected| *selected);
+                for value in self.selection.values_mut() {
+                    *value = selected;
+                }
+                (Task::none(), None)
+            }
+            Message::ToggleSelection(entry) => {
+                if let Some(selected) = self.selection.get_mut(&entry) {
+                    *selected = !*selected;
+                }
+                (Task::none(), None)
+            }
+
+            Message::Restore => {
+                let entries = self
+                    .selection
+                    .iter()
+                    .filter_map(|(entry, selected)| selected.then_some(entry.clone()))
+                    .collect::<Vec<_>>();
+
+                match entries.is_empty() {
+                    true => (Task::none(), None),
+                    false => {
+                        self.enabled = false;
+                        (
+                            Task::perform(
+                                restore(entries, self.saves_dir.clone(), self.backup_dir.clone()),
+                                Message::Restored,
+                            ),
+                            None,
+                        )
+                    }
+                }
+            }
+            Message::Restored(result) => {
+                self.enabled = true;
+
+                match result {
+                    Ok(count) => self.notify(format!("restored {} saves", count)),
+                    Err(err) => self.notify(format!("error restoring saves: {}", err)),
+                }
+
+                // The safety backup shows up as a new run
+                (self.open(self.saves_dir.clone()), None)
+            }
+
+            Message::Close => (Task::none(), Some(Event::Close)),
+        }
+    }
+
+    fn view_runs(&self) -> Element<Message> {
+        scrollable(self.runs.iter().enumerate().fold(
+            Column::new().spacing(2),
+            |column, (index, run)| {
+                let style = match self.run == Some(index) {
+                    true => theme::button::entry_selected,
+                    false => theme::button::entry_unselected,
+                };
+
+                column.push(
+                    button(text(format!("{} ({})", run.name, run.entries.len())))
+                        .style(style)
+                        .on_press_maybe(self.enabled.then_some(Message::SelectRun(index)))
+                        .width(Length::Fill),
+                )
+            },
+        ))
+        .into()
+    }
+
+    fn view_entries(&self) -> Element<Message> {
+        scrollable(self.selection.iter().fold(
+            Column::new().spacing(2),
+            |column, (entry, selected)| {
+                column.push(widget::entry::selectable(
+                    &entry.name,
+                    *selected,
+                    self.enabled
+                        .then_some(Message::ToggleSelection(entry.clone())),
+                ))
+            },
+        ))
+        .into()
+    }
+
+    fn view_manifest(&self, manifest: &Manifest) -> Element<Message> {
+        let source = manifest
+            .source
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let targets = manifest
+            .targets
+            .iter()
+            .fold(Column::new().spacing(2), |column, target| {
+                let name = target
+                    .path
+                    .file_stem()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                let style = match &target.result {
+                    Outcome::Pending => theme::button::entry_warning,
+                    Outcome::Success => theme::button::entry_success,
+                    Outcome::Error(_) => theme::button::entry_danger,
+                };
+                let label = match &target.result {
+                    Outcome::Error(err) => format!("{}: {}", name, err),
+                    _ => name,
+                };
+
+                column.push(button(text(label)).style(style).width(Length::Fill))
+            });
+
+        Column::new()
+            .spacing(10)
+            .push(text(format!("from {}", source)))
+            .push(text(format!(
+                "copied: {}",
+                manifest.components.iter().collect::<Vec<_>>().join(", ")
+            )))
+            .push(scrollable(targets))
+            .into()
+    }
+
+    fn view_left(&self) -> Column<Message> {
+        Column::new()
+            .spacing(10)
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_y(Vertical::Center)
+                    .height(Length::Shrink)
+                    .push(
+                        button(text("back"))
+                            .style(theme::button::secondary)
+                            .on_press_maybe(self.enabled.then_some(Message::Close)),
+                    )
+                    .push(heading("backups")),
+            )
+            .push(
+                button(text("refresh").font(Font {
+                    weight: Weight::Semibold,
+                    ..Default::default()
+                }))
+                .style(theme::button::secondary)
+                .on_press_maybe(self.enabled.then_some(Message::Refresh))
+                .width(Length::Fill),
+            )
+            .push(self.view_runs())
+    }
+
+    fn view_center(&self) -> Column<Message> {
+        Column::new()
+            .spacing(10)
+            .push(heading("saves"))
+            .push(
+                button(
+                    text(match self.selection.values().all(|selected| *selected) {
+                        true => "deselect all",
+                        false => "select all",
+                    })
+                    .font(Font {
+                        weight: Weight::Semibold,
+                        ..Default::default()
+                    }),
+                )
+                .style(theme::button::secondary)
+                .on_press_maybe(self.enabled.then_some(Message::ToggleSelectAll))
+                .width(Length::Fill),
+            )
+            .push(self.view_entries())
+    }
+
+    fn view_right(&self) -> Column<Message> {
+        let manifest = self
+            .run
+            .and_then(|index| self.runs[index].manifest.as_ref());
+
+        Column::new()
+            .spacing(10)
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_y(Vertical::Center)
+                    .push(
+                        button(text("\u{E803}").font(fonts::ICONS_FONT))
+                            .on_press_maybe(self.enabled.then_some(Message::Restore)),
+                    )
+                    .push(heading("restore")),
+            )
+            .push_maybe(self.message.as_ref().map(|message| {
+                container(text(message.clone()))
+                    .style(theme::container::monobox)
+                    .padding(10)
+                    .width(Length::Fill)
+            }))
+            .push_maybe(manifest.map(|manifest| self.view_manifest(manifest)))
+    }
+
+    pub fn view(&self) -> Element<Message> {
+        Row::new()
+            .push(self.view_left().width(Length::Fill))
+            .push(self.view_center().width(Length::Fill))
+            .push(self.view_right().width(Length::Fill))
+            .width(Length::Fill)
+            .spacing(10)
+            .into()
+    }
+}
+
+/// Lists the runs in `backup_dir`, newest first.
+async fn load_runs(backup_dir: PathBuf) -> Result<Vec<Run>> {
+    if !backup_dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut dirs = vec![];
+    let mut read_dir = fs::read_dir(&backup_dir).await?;
+    while let Some(entry) = read_dir.next_entry().await? {
+        if entry.file_type().await?.is_dir() {
+            dirs.push(entry.path());
+        }
+    }
+
+    // Runs are named by their timestamp, so this sorts them by time
+    dirs.sort();
+    dirs.reverse();
+
+    let mut runs = vec![];
+    for dir in dirs {
+        let manifest_path = dir.join(manifest::MANIFEST_FILENAME);
+        let manifest = match manifest_path.is_file() {
+            true => match ron::from_str(&fs::read_to_string(&manifest_path).await?) {
+                Ok(manifest) => Some(manifest),
+                Err(err) => {
+                    // Manifests written by older versions may not match the current format
+                    log::warn!("could not read manifest {:?}: {}", manifest_path, err);
+                    None
+                }
+            },
+            false => None,
+        };
+
+        runs.push(Run {
+            name: dir
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            entries: thugpro::entry::find_entries(&dir)?,
+            manifest,
+        });
+    }
+
+    Ok(runs)
+}
+
+/// Copies backed up saves back into `saves_dir`, first backing up the saves they replace.
+async fn restore(
+    entries: Vec<thugpro::Entry>,
+    saves_dir: Option<PathBuf>,
+    backup_dir: PathBuf,
+) -> Result<usize> {
+    let saves_dir = saves_dir.ok_or(Error::NoSavesDir)?;
+
+    let safety_dir = paths::create_run_dir(&backup_dir)?;
+
+    for entry in &entries {
+        let target = entry.with_dir(&saves_dir);
+        let target_filepath = target.filepath();
+
+        if target_filepath.is_file() {
+            log::info!(
+                "backing up {:?} to {:?} before restoring",
+                target_filepath,
+                safety_dir
+            );
+            fs::copy(&target_filepath, target.with_dir(&safety_dir).filepath()).await?;
+        }
+    }
+
+    for entry in &entries {
+        restore_entry(entry, &saves_dir).await?;
+    }
+
+    Ok(entries.len())
+}
+
+async fn restore_entry(entry: &thugpro::Entry, saves_dir: &Path) -> Result<()> {
+    let bytes = fs::read(entry.filepath()).await?;
+
+    // Keeps the metadata of the backup, so its modification time is restored too
+    let target = entry.with_dir(saves_dir);
+
+    log::info!(
+        "restoring {:?} to {:?}",
+        entry.filepath(),
+        target.filepath()
+    );
+
+    thugpro::entry::write_atomic(target.filepath(), &bytes)?;
+    target.overwrite_metadata()?;
+
+    Ok(())
+}
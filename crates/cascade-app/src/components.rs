@@ -0,0 +1,110 @@
+use std::collections::BTreeSet;
+
+use cascade_save as save;
+use cascade_thugpro as thugpro;
+use serde::{Deserialize, Serialize};
+use thugpro::trickset::{Part, SlotSpecials};
+
+/// The group the parts of a trickset can be chosen from instead.
+pub const TRICKSET: &str = "trickset";
+
+/// The group the grind specials can be chosen from instead.
+pub const SPECIALS: &str = "specials";
+
+/// The group that copies `part` along with everything else in it.
+pub fn part_group(part: Part) -> &'static str {
+    match part {
+        Part::Specials | Part::GrindSpecials => SPECIALS,
+        _ => TRICKSET,
+    }
+}
+
+/// The name a part of the trickset is chosen by, e.g. `trickset.flips`.
+pub fn part_name(part: Part) -> String {
+    format!("{}.{}", part_group(part), part.name())
+}
+
+/// What a run copies onto each save.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Transform {
+    pub cas: thugpro::Cas,
+    /// Specials that go on the empty slots of each save, so they are only placed once its
+    /// specials are known.
+    pub specials: Option<SlotSpecials>,
+}
+
+impl Transform {
+    /// The cas to apply to `save`.
+    pub fn cas(&self, save: &save::Save) -> thugpro::Result<thugpro::Cas> {
+        let mut cas = self.cas.clone();
+
+        if let Some(specials) = &self.specials {
+            let item = specials.item(&self.cas, save)?;
+            cas.data.extend([item]);
+        }
+
+        Ok(cas)
+    }
+}
+
+/// The names of the groups chosen to be copied from the source save.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Components(BTreeSet<String>);
+
+impl Components {
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains(name)
+    }
+
+    pub fn set(&mut self, name: &str, selected: bool) {
+        match selected {
+            true => self.0.insert(name.to_string()),
+            false => self.0.remove(name),
+        };
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    /// A transform copying the chosen groups of `source`, and leaving everything else alone.
+    ///
+    /// Groups that no longer exist are skipped. Parts of the trickset are only copied if the
+    /// group they belong to is not.
+    pub fn transform(
+        &self,
+        groups: &thugpro::Groups,
+        source: &save::Save,
+    ) -> thugpro::Result<Transform> {
+        let mut cas = thugpro::Cas {
+            summary: thugpro::cas::Summary::try_from(source.summary.clone())?,
+            ..Default::default()
+        };
+
+        for group in groups.iter().filter(|group| self.contains(&group.name)) {
+            cas.data.extend(groups.items(&group.name, &source.data)?);
+        }
+
+        let parts: Vec<_> = Part::MAPPINGS
+            .into_iter()
+            .chain([Part::GrindSpecials])
+            .filter(|part| !self.contains(part_group(*part)) && self.contains(&part_name(*part)))
+            .collect();
+
+        let mut specials = None;
+        if !parts.is_empty() {
+            let data = thugpro::Cas::try_from(source.clone())?.data;
+            let trickset = thugpro::Trickset::try_from(&data)?;
+
+            for part in parts {
+                match part {
+                    Part::GrindSpecials => specials = Some(trickset.grind_specials()),
+                    part => cas.data.extend(trickset.part_items(part)),
+                }
+            }
+        }
+
+        Ok(Transform { cas, specials })
+    }
+}
@@ -3,9 +3,10 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use cascade_thugpro as thugpro;
 use serde::{Deserialize, Serialize};
 
-use crate::{config::Error, paths};
+use crate::{components::Components, config::Error, paths};
 
 fn default_scale_factor() -> f64 {
     1.
@@ -35,9 +36,13 @@ pub struct Config {
     #[serde(default)]
     pub default_selection: bool,
     #[serde(default)]
-    pub scales: bool,
-    #[serde(default)]
-    pub trickset: bool,
+    pub components: Components,
+
+    // Replaced by `components`, only read to migrate older configs
+    #[serde(default, skip_serializing)]
+    scales: bool,
+    #[serde(default, skip_serializing)]
+    trickset: bool,
 }
 
 impl Default for Config {
@@ -47,6 +52,7 @@ impl Default for Config {
             source_path: None,
             scale_factor: 1.,
             default_selection: true,
+            components: Components::default(),
             scales: false,
             trickset: false,
         }
@@ -65,4 +71,21 @@ impl Config {
 
         Ok(config)
     }
+
+    /// Moves the options of older configs into `components`.
+    pub fn migrate(&mut self, groups: &thugpro::Groups) {
+        if self.trickset {
+            self.components.set("trickset", true);
+            self.components.set("specials", true);
+        }
+
+        if self.scales {
+            for group in groups.in_section("scales") {
+                self.components.set(&group.name, true);
+            }
+        }
+
+        self.trickset = false;
+        self.scales = false;
+    }
 }
@@ -13,15 +13,18 @@ use iced::{
     alignment::Vertical,
     font::Weight,
     widget::{button, checkbox, container, scrollable, text, tooltip},
-    Font, Length, Task,
+    Font, Length, Padding, Task,
 };
 use indexmap::IndexMap;
 use rfd::AsyncFileDialog;
 use tokio::fs;
 
 use crate::{
-    config::Selections,
-    fonts, paths, tasks, theme,
+    components::{self, Components, Transform},
+    config::{Format, Selections},
+    fonts,
+    manifest::{self, Manifest, Outcome},
+    paths, tasks, theme,
     widget::{self, heading},
     Column, Element, Row,
 };
@@ -48,6 +51,12 @@ pub enum Error {
 
     #[error("no saves dir set")]
     NoSavesDir,
+
+    #[error("written save does not match the transform")]
+    NotApplied,
+
+    #[error("{0} (restored from backup)")]
+    RolledBack(Box<Error>),
 }
 
 impl From<io::Error> for Error {
@@ -67,16 +76,10 @@ enum Status {
     Error(Error),
 }
 
-#[derive(Debug, Default, Clone, Copy)]
-pub struct Components {
-    trickset: bool,
-    scales: bool,
-}
-
 #[derive(Debug, Clone)]
 pub enum Message {
     LoadedCandidates(Result<IndexMap<thugpro::Entry, bool>>),
-    LoadedSource(Result<thugpro::Cas>),
+    LoadedSource(Result<save::Save>),
 
     PickSource,
     SourcePicked(Option<PathBuf>),
@@ -87,12 +90,13 @@ pub enum Message {
 
     ToggleSelectAll,
     ToggleSelection(thugpro::Entry),
-    ToggleTricksetComponent(bool),
-    ToggleScalesComponent(bool),
+    ToggleComponent(String, bool),
 
     Start,
-    PreProcessDone(Result<(Arc<thugpro::Cas>, PathBuf)>),
-    EntryProcessed(thugpro::Entry, Result<()>),
+    OpenBackups,
+    PreProcessDone(Result<(Arc<Transform>, PathBuf, Manifest)>),
+    EntryProcessed(thugpro::Entry, Result<u32>),
+    ManifestWritten(Result<()>),
 }
 
 #[derive(Debug, Clone)]
@@ -101,8 +105,8 @@ pub enum Event {
     SetSourcePath(PathBuf),
     SetDefaultSelection(bool, Selections),
     SetSelections(Selections),
-    SetTrickset(bool),
-    SetScales(bool),
+    SetComponents(Components),
+    OpenBackups,
 }
 
 pub struct Dashboard {
@@ -112,11 +116,14 @@ pub struct Dashboard {
     enabled: bool,
 
     source_entry: Option<thugpro::Entry>,
-    source: Option<thugpro::Cas>,
+    source: Option<save::Save>,
 
     candidates: IndexMap<thugpro::Entry, bool>,
+    groups: Arc<thugpro::Groups>,
     components: Components,
     queue: IndexMap<thugpro::Entry, Status>,
+    /// The backup dir and manifest of the current run.
+    run: Option<(PathBuf, Manifest)>,
 
     warning_message: Option<String>,
 }
@@ -128,8 +135,8 @@ impl Dashboard {
         backup_dir: PathBuf,
         default_selection: bool,
         selections: Selections,
-        trickset: bool,
-        scales: bool,
+        groups: Arc<thugpro::Groups>,
+        components: Components,
     ) -> (Self, Task<Message>) {
         let source_entry = source_path
             .map(|path| thugpro::Entry::at_path(path).ok())
@@ -154,8 +161,10 @@ impl Dashboard {
             saves_dir,
             candidates: IndexMap::new(),
             queue: IndexMap::new(),
+            run: None,
             default_selection,
-            components: Components { scales, trickset },
+            groups,
+            components,
             warning_message: None,
         };
 
@@ -261,14 +270,12 @@ impl Dashboard {
                 (Task::none(), Some(Event::SetSelections(self.selections())))
             }
 
-            Message::ToggleTricksetComponent(selected) => {
-                self.components.trickset = selected;
-                (Task::none(), Some(Event::SetTrickset(selected)))
-            }
-
-            Message::ToggleScalesComponent(selected) => {
-                self.components.scales = selected;
-                (Task::none(), Some(Event::SetScales(selected)))
+            Message::ToggleComponent(name, selected) => {
+                self.components.set(&name, selected);
+                (
+                    Task::none(),
+                    Some(Event::SetComponents(self.components.clone())),
+                )
             }
 
             Message::Start => match &self.source {
@@ -276,22 +283,28 @@ impl Dashboard {
                     if self.candidates.values().any(|selected| *selected) {
                         self.enabled = false;
 
-                        let datetime = time::OffsetDateTime::now_local()
-                            .unwrap_or(time::OffsetDateTime::now_utc());
+                        let source_path = self
+                            .source_entry
+                            .as_ref()
+                            .map(|entry| entry.filepath())
+                            .unwrap_or_default();
 
-                        let backup_dir = self.backup_dir.join(format!(
-                            "{:04}-{:02}-{:02}T{:02}-{:02}-{:02}",
-                            datetime.year(),
-                            u8::from(datetime.month()),
-                            datetime.day(),
-                            datetime.hour(),
-                            datetime.minute(),
-                            datetime.second()
-                        ));
+                        let targets = self
+                            .candidates
+                            .iter()
+                            .filter_map(|(entry, selected)| selected.then_some(entry.clone()))
+                            .collect::<Vec<_>>();
 
                         (
                             Task::perform(
-                                pre_process(backup_dir, source.clone(), self.components),
+                                pre_process(
+                                    self.backup_dir.clone(),
+                                    source.clone(),
+                                    source_path,
+                                    Arc::clone(&self.groups),
+                                    self.components.clone(),
+                                    targets,
+                                ),
                                 Message::PreProcessDone,
                             ),
                             None,
@@ -302,7 +315,10 @@ impl Dashboard {
                 }
                 None => (Task::none(), None),
             },
-            Message::PreProcessDone(Ok((transform, backup_dir))) => {
+            Message::OpenBackups => (Task::none(), Some(Event::OpenBackups)),
+            Message::PreProcessDone(Ok((transform, backup_dir, manifest))) => {
+                self.run = Some((backup_dir.clone(), manifest));
+
                 let selected_entries = self
                     .candidates
                     .iter()
@@ -341,6 +357,20 @@ impl Dashboard {
                 (Task::none(), None)
             }
             Message::EntryProcessed(entry, result) => {
+                if let Some(target) = self
+                    .run
+                    .as_mut()
+                    .and_then(|(_, manifest)| manifest.target_mut(&entry.filepath()))
+                {
+                    match &result {
+                        Ok(checksum) => {
+                            target.after = Some(*checksum);
+                            target.result = Outcome::Success;
+                        }
+                        Err(err) => target.result = Outcome::Error(err.to_string()),
+                    }
+                }
+
                 let new_status = match result {
                     Ok(_) => Status::Success,
                     Err(err) => {
@@ -360,6 +390,23 @@ impl Dashboard {
                     self.enabled = true;
                 }
 
+                match self.run.take() {
+                    Some((backup_dir, manifest)) if manifest.is_done() => (
+                        Task::perform(
+                            write_manifest(manifest, backup_dir),
+                            Message::ManifestWritten,
+                        ),
+                        None,
+                    ),
+                    run => {
+                        self.run = run;
+                        (Task::none(), None)
+                    }
+                }
+            }
+            Message::ManifestWritten(Ok(())) => (Task::none(), None),
+            Message::ManifestWritten(Err(err)) => {
+                self.notify(format!("error writing manifest: {}", err));
                 (Task::none(), None)
             }
         }
@@ -472,15 +519,73 @@ impl Dashboard {
                     .push(heading("from"))
                     .push(self.view_source_info()),
             )
-            .push(
-                checkbox("trickset", self.components.trickset)
-                    .on_toggle_maybe(self.enabled.then_some(Message::ToggleTricksetComponent)),
-            )
-            .push(
-                checkbox("scales", self.components.scales)
-                    .on_toggle_maybe(self.enabled.then_some(Message::ToggleScalesComponent)),
-            )
-            .push(Row::new().height(Length::Fill).align_y(Vertical::Bottom))
+            .push(scrollable(self.view_components()))
+    }
+
+    fn view_components(&self) -> Column<Message> {
+        self.groups
+            .sections()
+            .into_iter()
+            .fold(Column::new().spacing(10), |column, section| {
+                let groups = self.groups.in_section(section).fold(
+                    Column::new().spacing(4),
+                    |column, group| {
+                        let name = group.name.clone();
+
+                        let column = column.push(
+                            checkbox(group.label(), self.components.contains(&group.name))
+                                .on_toggle_maybe(self.enabled.then_some(move |selected| {
+                                    Message::ToggleComponent(name.clone(), selected)
+                                })),
+                        );
+
+                        match group.name.as_str() {
+                            components::TRICKSET => column.push(self.view_parts(
+                                components::TRICKSET,
+                                &thugpro::trickset::Part::MAPPINGS,
+                            )),
+                            components::SPECIALS => column.push(self.view_parts(
+                                components::SPECIALS,
+                                &[thugpro::trickset::Part::GrindSpecials],
+                            )),
+                            _ => column,
+                        }
+                    },
+                );
+
+                column
+                    .push(text(section).font(Font {
+                        weight: Weight::Semibold,
+                        ..Default::default()
+                    }))
+                    .push(groups)
+            })
+    }
+
+    /// Checkboxes for the parts of `group`, which are all copied with the whole of it.
+    fn view_parts(&self, group: &str, parts: &[thugpro::trickset::Part]) -> Element<Message> {
+        let whole = self.components.contains(group);
+
+        let parts = parts
+            .iter()
+            .copied()
+            .fold(Column::new().spacing(4), |column, part| {
+                let name = components::part_name(part);
+
+                column.push(
+                    checkbox(part.label(), whole || self.components.contains(&name))
+                        .on_toggle_maybe((self.enabled && !whole).then_some(move |selected| {
+                            Message::ToggleComponent(name.clone(), selected)
+                        })),
+                )
+            });
+
+        container(parts)
+            .padding(Padding {
+                left: 20.0,
+                ..Padding::ZERO
+            })
+            .into()
     }
 
     fn view_center(&self) -> Column<Message> {
@@ -527,7 +632,13 @@ impl Dashboard {
                         button(text("\u{E803}").font(fonts::ICONS_FONT))
                             .on_press_maybe(self.enabled.then_some(Message::Start)),
                     )
-                    .push(heading("queue")),
+                    .push(heading("queue"))
+                    .push(Row::new().width(Length::Fill))
+                    .push(
+                        button(text("backups"))
+                            .style(theme::button::secondary)
+                            .on_press_maybe(self.enabled.then_some(Message::OpenBackups)),
+                    ),
             )
             .push(self.view_queue())
     }
@@ -554,14 +665,16 @@ async fn pick_source() -> Option<PathBuf> {
     )
 }
 
-async fn _load_source(entry: thugpro::Entry) -> Result<thugpro::Cas> {
+async fn _load_source(entry: thugpro::Entry) -> Result<save::Save> {
     let save = save::Save::read(&mut entry.reader()?)?;
-    let cas = thugpro::Cas::try_from(save)?;
 
-    Ok(cas)
+    // Only saves cascade can make sense of are worth copying from
+    thugpro::Cas::try_from(save.clone())?;
+
+    Ok(save)
 }
 
-async fn load_source(entry: thugpro::Entry) -> Result<thugpro::Cas> {
+async fn load_source(entry: thugpro::Entry) -> Result<save::Save> {
     let source = tokio::spawn(async move { _load_source(entry).await })
         .await
         .map_err(|_| Error::Task)?;
@@ -592,120 +705,60 @@ async fn load_candidates(
     Ok(candidates)
 }
 
-fn make_transform(source: &thugpro::cas::Cas, components: Components) -> thugpro::cas::Cas {
-    let info = &source.data.custom_skater.custom.info;
-    let appearance = &source.data.custom_skater.custom.appearance;
-
-    thugpro::cas::Cas {
-        summary: source.summary.clone(),
-        data: thugpro::cas::Data {
-            custom_skater: thugpro::cas::CustomSkater {
-                custom: thugpro::cas::Custom {
-                    info: thugpro::cas::Info {
-                        trick_mapping: components
-                            .trickset
-                            .then_some(info.trick_mapping.clone())
-                            .unwrap_or_default(),
-                        specials: components
-                            .trickset
-                            .then_some(info.specials.clone())
-                            .unwrap_or_default(),
-                    },
-                    appearance: thugpro::cas::Appearance {
-                        board_bone_group: components
-                            .scales
-                            .then_some(appearance.board_bone_group.clone())
-                            .unwrap_or_default(),
-                        feet_bone_group: components
-                            .scales
-                            .then_some(appearance.feet_bone_group.clone())
-                            .unwrap_or_default(),
-                        hands_bone_group: components
-                            .scales
-                            .then_some(appearance.hands_bone_group.clone())
-                            .unwrap_or_default(),
-                        head_bone_group: components
-                            .scales
-                            .then_some(appearance.head_bone_group.clone())
-                            .unwrap_or_default(),
-                        headtop_bone_group: components
-                            .scales
-                            .then_some(appearance.headtop_bone_group.clone())
-                            .unwrap_or_default(),
-                        jaw_bone_group: components
-                            .scales
-                            .then_some(appearance.jaw_bone_group.clone())
-                            .unwrap_or_default(),
-                        lower_arm_bone_group: components
-                            .scales
-                            .then_some(appearance.lower_arm_bone_group.clone())
-                            .unwrap_or_default(),
-                        lower_leg_bone_group: components
-                            .scales
-                            .then_some(appearance.lower_leg_bone_group.clone())
-                            .unwrap_or_default(),
-                        nose_bone_group: components
-                            .scales
-                            .then_some(appearance.nose_bone_group.clone())
-                            .unwrap_or_default(),
-                        object_scaling: components
-                            .scales
-                            .then_some(appearance.object_scaling.clone())
-                            .unwrap_or_default(),
-                        stomach_bone_group: components
-                            .scales
-                            .then_some(appearance.stomach_bone_group.clone())
-                            .unwrap_or_default(),
-                        torso_bone_group: components
-                            .scales
-                            .then_some(appearance.torso_bone_group.clone())
-                            .unwrap_or_default(),
-                        upper_arm_bone_group: components
-                            .scales
-                            .then_some(appearance.upper_arm_bone_group.clone())
-                            .unwrap_or_default(),
-                        upper_leg_bone_group: components
-                            .scales
-                            .then_some(appearance.upper_leg_bone_group.clone())
-                            .unwrap_or_default(),
-                        ..Default::default()
-                    },
-                },
-            },
-            story_skater: thugpro::cas::StorySkater {
-                tricks: components
-                    .trickset
-                    .then_some(source.data.story_skater.tricks.clone())
-                    .unwrap_or_default(),
-            },
-        },
-    }
-}
-
 async fn pre_process<P: AsRef<Path>>(
     backup_dir: P,
-    source: thugpro::Cas,
+    source: save::Save,
+    source_path: PathBuf,
+    groups: Arc<thugpro::Groups>,
     components: Components,
-) -> Result<(Arc<thugpro::Cas>, PathBuf)> {
-    let backup_dir = backup_dir.as_ref();
-    fs::create_dir_all(backup_dir).await?;
-    let transform = Arc::new(make_transform(&source, components));
+    targets: Vec<thugpro::Entry>,
+) -> Result<(Arc<Transform>, PathBuf, Manifest)> {
+    let backup_dir = &paths::create_run_dir(backup_dir)?;
+    let transform = Arc::new(components.transform(&groups, &source)?);
+
+    let targets = targets
+        .iter()
+        .map(|entry| -> Result<manifest::Target> {
+            Ok(manifest::Target {
+                path: entry.filepath(),
+                backup: entry.with_dir(backup_dir).filepath(),
+                before: save::Header::read(&mut entry.reader()?)?.checksum,
+                after: None,
+                result: Outcome::Pending,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let manifest = Manifest {
+        source: source_path,
+        components,
+        transform: (*transform).clone(),
+        targets,
+    };
+
+    // Written again with the results once every target is processed
+    write_manifest(manifest.clone(), backup_dir).await?;
+
+    Ok((Arc::clone(&transform), PathBuf::from(backup_dir), manifest))
+}
 
-    // tasks::write(
-    //     Arc::clone(&transform),
-    //     backup_dir.join("transform.ron"),
-    //     Format::Ron,
-    // )
-    // .await?;
+async fn write_manifest(manifest: Manifest, backup_dir: impl AsRef<Path>) -> Result<()> {
+    tasks::write(
+        manifest,
+        backup_dir.as_ref().join(manifest::MANIFEST_FILENAME),
+        Format::Ron,
+    )
+    .await?;
 
-    Ok((Arc::clone(&transform), PathBuf::from(backup_dir)))
+    Ok(())
 }
 
+/// Transforms a save, returning the header checksum it was written with.
 async fn process_entry<P: AsRef<Path>>(
     entry: thugpro::Entry,
     backup_dir: P,
-    transform: Arc<thugpro::Cas>,
-) -> Result<()> {
+    transform: Arc<Transform>,
+) -> Result<u32> {
     let backup_dir = backup_dir.as_ref();
 
     let backup_entry = entry.with_dir(backup_dir);
@@ -718,14 +771,37 @@ async fn process_entry<P: AsRef<Path>>(
 
     let mut save = save::Save::read(&mut entry.reader()?)?;
 
-    transform.modify(&mut save)?;
-    save.write(&mut entry.writer()?)?;
+    let cas = transform.cas(&save)?;
+    cas.modify(&mut save)?;
+    entry.write_save(&save)?;
 
     log::info!("overwrote save at {:?}", filepath);
 
-    entry.overwrite_metadata()?;
+    if let Err(err) = verify_entry(&entry, &cas) {
+        log::error!(
+            "verifying {:?} failed, restoring from {:?}: {}",
+            filepath,
+            backup_filepath,
+            err
+        );
 
-    Ok(())
+        thugpro::entry::write_atomic(&filepath, &fs::read(&backup_filepath).await?)?;
+        entry.overwrite_metadata()?;
+
+        return Err(Error::RolledBack(Box::new(err)));
+    }
+
+    Ok(save.calculate_header()?.checksum)
+}
+
+/// Checks that a written save loads, checksums included, and has its cas applied.
+fn verify_entry(entry: &thugpro::Entry, cas: &thugpro::Cas) -> Result<()> {
+    let save = save::Save::read(&mut entry.reader()?)?;
+
+    match cas.is_applied(&save)? {
+        true => Ok(()),
+        false => Err(Error::NotApplied),
+    }
 }
 
 async fn pick_saves_dir() -> Option<PathBuf> {
@@ -1,9 +1,10 @@
 #![cfg_attr(target_os = "windows", windows_subsystem = "windows")]
 #![feature(error_generic_member_access, path_add_extension)]
 
-use std::{io, path::Path, result};
+use std::{io, path::Path, result, sync::Arc};
 
 use app::Cascade;
+use cascade_thugpro as thugpro;
 use clap::Parser;
 use config::{Config, Selections};
 use fern::colors::{Color, ColoredLevelConfig};
@@ -11,9 +12,12 @@ use iced::{window, Size};
 use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 
 mod app;
+mod backups;
+mod components;
 mod config;
 mod dashboard;
 mod fonts;
+mod manifest;
 mod paths;
 mod tasks;
 mod theme;
@@ -98,7 +102,13 @@ fn main() -> Result<()> {
 
     configure_logging(paths::log(&cascade_dir))?;
 
-    let config = Config::load(paths::config(&cascade_dir)).unwrap_or_default();
+    let groups = thugpro::Groups::load(paths::groups(&cascade_dir)).unwrap_or_else(|err| {
+        log::error!("could not load groups, using the bundled ones: {}", err);
+        thugpro::Groups::embedded().expect("could not load bundled groups")
+    });
+
+    let mut config = Config::load(paths::config(&cascade_dir)).unwrap_or_default();
+    config.migrate(&groups);
     log::info!("loaded config: {:?}", config);
 
     let selections = Selections::load(paths::selections(&cascade_dir)).unwrap_or_default();
@@ -118,7 +128,16 @@ fn main() -> Result<()> {
         .font(fonts::ICONS_FONT_BYTES)
         .scale_factor(Cascade::scale_factor)
         .subscription(Cascade::subscription)
-        .run_with(move || Cascade::new((cascade_dir, config, selections, theme, debug)))?;
+        .run_with(move || {
+            Cascade::new((
+                cascade_dir,
+                config,
+                selections,
+                theme,
+                Arc::new(groups),
+                debug,
+            ))
+        })?;
 
     Ok(())
 }
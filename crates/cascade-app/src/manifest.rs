@@ -0,0 +1,48 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::components::{Components, Transform};
+
+pub const MANIFEST_FILENAME: &str = "manifest.ron";
+
+/// A record of what a cascade run did, kept in its backup dir.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub source: PathBuf,
+    pub components: Components,
+    pub transform: Transform,
+    pub targets: Vec<Target>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Target {
+    pub path: PathBuf,
+    pub backup: PathBuf,
+    /// The header checksum of the save before the run.
+    pub before: u32,
+    /// The header checksum of the save the run wrote, if it kept it.
+    pub after: Option<u32>,
+    pub result: Outcome,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Outcome {
+    Pending,
+    Success,
+    Error(String),
+}
+
+impl Manifest {
+    pub fn target_mut(&mut self, path: &Path) -> Option<&mut Target> {
+        self.targets
+            .iter_mut()
+            .find(|target| target.path.as_path() == path)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.targets
+            .iter()
+            .all(|target| !matches!(target.result, Outcome::Pending))
+    }
+}
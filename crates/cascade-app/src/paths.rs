@@ -4,7 +4,10 @@ use std::{
     result,
 };
 
-const CONFIG_FILENAME: &'static str = "cascade.toml";
+use cascade_lut as lut;
+use cascade_thugpro as thugpro;
+use lut::dirs::CONFIG_FILENAME;
+
 const SELECTIONS_FILENAME: &'static str = "selections.ron";
 const THEME_FILENAME: &'static str = "theme.toml";
 const LOG_FILENAME: &'static str = "cascade.log";
@@ -69,31 +72,11 @@ fn cwd() -> Result<PathBuf> {
     Ok(exe.parent().ok_or(Error::Cwd)?.into())
 }
 
-fn portable_dir() -> Option<PathBuf> {
-    let cwd = cwd().ok()?;
-    let config_path = cwd.join(CONFIG_FILENAME);
-
-    match config_path.is_file() {
-        true => Some(cwd.to_path_buf()),
-        false => None,
-    }
-}
-
-fn default_cascade_dir() -> Result<PathBuf> {
-    // %localappdata%/cascade/
-    let path = local_appdata_dir().map(|dir| dir.join("cascade"))?;
-
-    if !path.is_dir() {
-        fs::create_dir_all(&path)?;
-    }
-
-    Ok(path)
-}
-
 pub fn cascade_dir() -> Result<PathBuf> {
-    match portable_dir() {
-        Some(dir) => Ok(dir),
-        None => default_cascade_dir().or_else(|_| cwd()),
+    // The portable dir, or else %localappdata%/cascade/, shared with the cli
+    match lut::cascade_dir() {
+        Some(dir) if dir.is_dir() || fs::create_dir_all(&dir).is_ok() => Ok(dir),
+        _ => cwd(),
     }
 }
 
@@ -102,6 +85,39 @@ pub fn backup_dir(cascade_dir: impl AsRef<Path>) -> PathBuf {
     cascade_dir.as_ref().join("backup")
 }
 
+/// Creates a new dir for one run in `backup_dir`, named after the current time. A dir is never
+/// reused, so runs in the same second get a suffix.
+pub fn create_run_dir(backup_dir: impl AsRef<Path>) -> Result<PathBuf> {
+    // %localappdata%/cascade/backup/2024-01-31T12-00-00/
+    let datetime = time::OffsetDateTime::now_local().unwrap_or(time::OffsetDateTime::now_utc());
+    let name = format!(
+        "{:04}-{:02}-{:02}T{:02}-{:02}-{:02}",
+        datetime.year(),
+        u8::from(datetime.month()),
+        datetime.day(),
+        datetime.hour(),
+        datetime.minute(),
+        datetime.second()
+    );
+
+    let backup_dir = backup_dir.as_ref();
+    fs::create_dir_all(backup_dir)?;
+
+    let mut dir = backup_dir.join(&name);
+    let mut suffix = 1;
+    loop {
+        match fs::create_dir(&dir) {
+            Ok(()) => return Ok(dir),
+            // e.g. %localappdata%/cascade/backup/2024-01-31T12-00-00-2/
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                suffix += 1;
+                dir = backup_dir.join(format!("{}-{}", name, suffix));
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
 pub fn config(cascade_dir: impl AsRef<Path>) -> PathBuf {
     // %localappdata%/cascade/cascade.toml
     cascade_dir.as_ref().join(CONFIG_FILENAME)
@@ -117,6 +133,11 @@ pub fn theme(cascade_dir: impl AsRef<Path>) -> PathBuf {
     cascade_dir.as_ref().join(THEME_FILENAME)
 }
 
+pub fn groups(cascade_dir: impl AsRef<Path>) -> PathBuf {
+    // %localappdata%/cascade/groups.ron
+    cascade_dir.as_ref().join(thugpro::groups::GROUPS_FILENAME)
+}
+
 pub fn log(cascade_dir: impl AsRef<Path>) -> PathBuf {
     // %localappdata%/cascade/cascade.log
     cascade_dir.as_ref().join(LOG_FILENAME)
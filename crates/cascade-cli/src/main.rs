@@ -1,8 +1,14 @@
-use std::{fmt::Debug, fs::File, io::Write, path::PathBuf};
+use std::{
+    fmt::Debug,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
 
 use cascade_dump as dump;
 use cascade_lut::{self as lut, Lut};
-use cascade_save::Save;
+use cascade_qb as qb;
+use cascade_save::{self as save, ReadOptions, Save};
 use cascade_thugpro as thugpro;
 use clap::{Args, Parser, Subcommand};
 
@@ -18,12 +24,60 @@ struct App {
 
 #[derive(Debug, Subcommand)]
 enum Command {
+    /// Dumps a save as text, to stdout if no output is given
     Dump {
         #[arg(short, long)]
         input: PathBuf,
 
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// One of ron, json, jsonl, toml or tree. Guessed from the output extension if not given
+        #[arg(short, long)]
+        format: Option<dump::Format>,
+    },
+    /// Writes an annotated hex dump of a save, to stdout if no output is given
+    Hex {
+        #[arg(short, long)]
+        input: PathBuf,
+
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Builds a save from a ron, json or toml dump
+    Build {
+        #[arg(short, long)]
+        input: PathBuf,
+
         #[arg(short, long)]
         output: PathBuf,
+
+        /// Guessed from the input extension if not given
+        #[arg(short, long)]
+        format: Option<dump::Format>,
+    },
+    /// Fixes the header checksums, sizes and padding of a damaged save. Only reports what would
+    /// be fixed if no output is given
+    Repair {
+        #[arg(short, long)]
+        input: PathBuf,
+
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    Diff {
+        #[arg(long)]
+        old: PathBuf,
+
+        #[arg(long)]
+        new: PathBuf,
+    },
+    /// Looks up names or ids (`#123`, `@c8:4`) in the lut, and the layer that named them
+    Lookup { queries: Vec<String> },
+    /// Shows the button combos and specials of a save, and anything wrong with them
+    Trickset {
+        #[arg(short, long)]
+        input: PathBuf,
     },
     Randomize {
         #[arg(long)]
@@ -37,6 +91,11 @@ enum Command {
 
         #[arg(long)]
         female: bool,
+
+        /// Groups to take from random saves, defaulting to the clothes and board. Groups joined
+        /// by commas, e.g. `hair,hat`, are taken from the same save
+        #[arg(long = "group")]
+        groups: Vec<String>,
     },
     RandomizeBulk {
         #[arg(long)]
@@ -50,34 +109,217 @@ enum Command {
 
         #[arg(long)]
         female: bool,
+
+        /// Groups to take from random saves, defaulting to the clothes and board. Groups joined
+        /// by commas, e.g. `hair,hat`, are taken from the same save
+        #[arg(long = "group")]
+        groups: Vec<String>,
     },
 }
 
 #[derive(Debug, Args)]
-struct GlobalOpts {}
+struct GlobalOpts {
+    /// Extra lut files, layered over the embedded and user luts
+    #[arg(long, global = true)]
+    lut: Vec<PathBuf>,
+
+    /// Extra group files, over the embedded and user groups
+    #[arg(long = "groups", global = true)]
+    group_files: Vec<PathBuf>,
+
+    /// Read saves even if their checksums or sizes don't match their headers
+    #[arg(long, global = true)]
+    lenient: bool,
+}
+
+impl GlobalOpts {
+    fn read_options(&self) -> ReadOptions {
+        match self.lenient {
+            true => ReadOptions::lenient(),
+            false => ReadOptions::strict(),
+        }
+    }
+}
+
+fn guess_format(path: &Path) -> Option<dump::Format> {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .and_then(dump::Format::from_extension)
+}
+
+fn load_lut(files: &[PathBuf]) -> color_eyre::Result<lut::Layered> {
+    let mut layered = lut::Layered::new();
+
+    layered.push(
+        lut::Source::Embedded,
+        Lut {
+            checksum: lut::Checksum::load()?,
+            compress: thugpro::lut::load_compress()?,
+        },
+    );
+
+    if let Some(dir) = lut::user_dir().filter(|dir| dir.is_dir()) {
+        layered.push_dir(dir)?;
+    }
+
+    for file in files {
+        layered.push_file(file)?;
+    }
+
+    Ok(layered)
+}
+
+fn load_groups(files: &[PathBuf]) -> color_eyre::Result<thugpro::Groups> {
+    let mut groups = match lut::cascade_dir() {
+        Some(dir) => thugpro::Groups::load(dir.join(thugpro::groups::GROUPS_FILENAME))?,
+        None => thugpro::Groups::embedded()?,
+    };
+
+    for file in files {
+        groups.merge(thugpro::groups::parse(&fs::read_to_string(file)?)?);
+    }
+
+    Ok(groups)
+}
+
+fn group_bundles(names: &[String]) -> Vec<Vec<&str>> {
+    match names.is_empty() {
+        true => thugpro::random::BUNDLES
+            .iter()
+            .map(|bundle| bundle.to_vec())
+            .collect(),
+        false => names.iter().map(|name| name.split(',').collect()).collect(),
+    }
+}
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
 
-    let App {
-        global: _global,
-        command,
-    } = App::parse();
+    let App { global, command } = App::parse();
 
     match command {
-        Command::Dump { input, output } => {
+        Command::Dump {
+            input,
+            output,
+            format,
+        } => {
             let entry = thugpro::Entry::at_path(&input)?;
-            let save = Save::read(&mut entry.reader()?)?;
-            let lut = Lut {
-                checksum: lut::Checksum::load()?,
-                compress: thugpro::lut::load_compress()?,
-            };
-            let dump = dump::Save::new(&save, &lut);
-
-            let mut file = File::create(output).unwrap();
-            let contents =
-                ron::ser::to_string_pretty(&dump, ron::ser::PrettyConfig::new()).unwrap();
-            file.write(contents.as_bytes()).unwrap();
+            let save = Save::read_with(&mut entry.reader()?, global.read_options())?;
+            let lut = load_lut(&global.lut)?;
+            let dump = dump::Save::new(&save, lut.lut());
+
+            let format = format
+                .or_else(|| output.as_deref().and_then(guess_format))
+                .unwrap_or(dump::Format::Ron);
+
+            match output {
+                Some(output) => {
+                    let mut writer = BufWriter::new(File::create(output)?);
+                    format.write(&dump, &mut writer)?;
+                    writer.flush()?;
+                }
+                None => format.write(&dump, &mut io::stdout().lock())?,
+            }
+
+            Ok(())
+        }
+        Command::Hex { input, output } => {
+            let bytes = fs::read(&input)?;
+            let lut = load_lut(&global.lut)?;
+
+            match output {
+                Some(output) => {
+                    let mut writer = BufWriter::new(File::create(output)?);
+                    dump::hex::write(&bytes, lut.lut(), &mut writer)?;
+                    writer.flush()?;
+                }
+                None => dump::hex::write(&bytes, lut.lut(), &mut io::stdout().lock())?,
+            }
+
+            Ok(())
+        }
+        Command::Build {
+            input,
+            output,
+            format,
+        } => {
+            let format = format
+                .or_else(|| guess_format(&input))
+                .unwrap_or(dump::Format::Ron);
+
+            let dump = format.parse(&fs::read_to_string(&input)?)?;
+            let save = Save::try_from(&dump)?;
+
+            let mut bytes = vec![];
+            save.write(&mut bytes)?;
+            thugpro::entry::write_atomic(output, &bytes)?;
+
+            Ok(())
+        }
+        Command::Repair { input, output } => {
+            let (save, fixes) = save::repair(&fs::read(&input)?)?;
+
+            match fixes.is_empty() {
+                true => println!("nothing to fix"),
+                false => fixes.iter().for_each(|fix| println!("{}", fix)),
+            }
+
+            if let Some(output) = output {
+                let mut bytes = vec![];
+                save.write(&mut bytes)?;
+                thugpro::entry::write_atomic(output, &bytes)?;
+            }
+
+            Ok(())
+        }
+        Command::Diff { old, new } => {
+            let options = global.read_options();
+            let old = Save::read_with(&mut thugpro::Entry::at_path(&old)?.reader()?, options)?;
+            let new = Save::read_with(&mut thugpro::Entry::at_path(&new)?.reader()?, options)?;
+
+            print!("{}", qb::diff(&old.summary, &new.summary));
+            print!("{}", qb::diff(&old.data, &new.data));
+
+            Ok(())
+        }
+        Command::Trickset { input } => {
+            let entry = thugpro::Entry::at_path(&input)?;
+            let save = Save::read_with(&mut entry.reader()?, global.read_options())?;
+            let cas = thugpro::Cas::try_from(save)?;
+            let trickset = thugpro::Trickset::try_from(&cas.data)?;
+            let lut = load_lut(&global.lut)?;
+
+            print!("{}", trickset.display(lut.lut()));
+            for problem in trickset.validate() {
+                println!("warning: {}", problem);
+            }
+
+            Ok(())
+        }
+        Command::Lookup { queries } => {
+            let layered = load_lut(&global.lut)?;
+            let lut = layered.lut();
+
+            for query in queries {
+                let ids = match query.parse::<qb::Path>()?.segments() {
+                    [qb::Segment::Id(id)] => vec![*id],
+                    _ => qb::Resolve::resolve(lut, &query),
+                };
+
+                for id in ids {
+                    let name = match id {
+                        qb::Id::Checksum(checksum) => lut.checksum.lookup(checksum),
+                        qb::Id::Compress8(i) => lut.compress.lookup8(i),
+                        qb::Id::Compress16(i) => lut.compress.lookup16(i),
+                        qb::Id::None => None,
+                    };
+
+                    match (name, layered.source(id)) {
+                        (Some(name), Some(source)) => println!("{} = {} ({})", name, id, source),
+                        _ => println!("{} = {} (unnamed)", query, id),
+                    }
+                }
+            }
 
             Ok(())
         }
@@ -86,9 +328,20 @@ fn main() -> color_eyre::Result<()> {
             output_dir,
             name,
             female,
+            groups,
         } => {
             let entries = thugpro::entry::find_entries(input_dir).unwrap();
-            thugpro::random::randomize(&entries, output_dir, name, female)?;
+            thugpro::random::randomize(
+                &entries,
+                &load_groups(&global.group_files)?,
+                &group_bundles(&groups)
+                    .iter()
+                    .map(Vec::as_slice)
+                    .collect::<Vec<_>>(),
+                output_dir,
+                name,
+                female,
+            )?;
 
             Ok(())
         }
@@ -97,9 +350,20 @@ fn main() -> color_eyre::Result<()> {
             output_dir,
             number,
             female,
+            groups,
         } => {
             let entries = thugpro::entry::find_entries(input_dir).unwrap();
-            thugpro::random::randomize_bulk(&entries, output_dir, number, female)?;
+            thugpro::random::randomize_bulk(
+                &entries,
+                &load_groups(&global.group_files)?,
+                &group_bundles(&groups)
+                    .iter()
+                    .map(Vec::as_slice)
+                    .collect::<Vec<_>>(),
+                output_dir,
+                number,
+                female,
+            )?;
 
             Ok(())
         }
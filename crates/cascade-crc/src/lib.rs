@@ -1,20 +1,74 @@
-use std::cell::LazyCell;
-
-use crc::{Algorithm, Crc};
-
-const CRC: LazyCell<Crc<u32>> = LazyCell::new(|| {
-    Crc::<u32>::new(&Algorithm {
-        width: 32,
-        poly: 0x04c11db7,
-        init: 0xffffffff,
-        refin: true,
-        refout: true,
-        xorout: 0x0000,
-        check: 0xaee7,
-        residue: 0x0000,
-    })
+use std::io::{self, Write};
+
+use crc::{Algorithm, Crc, Digest};
+
+// Shared by save file checksums and QB keys
+static CRC: Crc<u32> = Crc::<u32>::new(&Algorithm {
+    width: 32,
+    poly: 0x04c11db7,
+    init: 0xffffffff,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+    check: 0xaee7,
+    residue: 0x0000,
 });
 
-pub fn checksum(bytes: &Vec<u8>) -> u32 {
-    CRC.checksum(bytes.as_slice())
+pub fn checksum(bytes: &[u8]) -> u32 {
+    CRC.checksum(bytes)
+}
+
+/// The QB key of `name`, as the game hashes symbol names into checksums.
+///
+/// Names are case-insensitive, and `/` hashes like `\`.
+pub const fn key(name: &str) -> u32 {
+    let bytes = name.as_bytes();
+    let mut digest = CRC.digest();
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = match bytes[i] {
+            b'/' => b'\\',
+            byte => byte.to_ascii_lowercase(),
+        };
+        digest.update(&[byte]);
+        i += 1;
+    }
+
+    digest.finalize()
+}
+
+/// Computes a [`checksum`] incrementally, without collecting the bytes first.
+#[derive(Clone)]
+pub struct Hasher(Digest<'static, u32>);
+
+impl Hasher {
+    pub fn new() -> Self {
+        Self(CRC.digest())
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    pub fn finalize(self) -> u32 {
+        self.0.finalize()
+    }
+}
+
+impl Default for Hasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
 }
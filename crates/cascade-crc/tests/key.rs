@@ -0,0 +1,28 @@
+use cascade_crc as crc;
+
+#[test]
+fn key_matches_game_checksums() {
+    assert_eq!(crc::key("total_score"), 236215964);
+    assert_eq!(crc::key("appearance"), 1431076207);
+    assert_eq!(crc::key("Total_Score"), crc::key("total_score"));
+    assert_eq!(crc::key("a/b"), crc::key("a\\b"));
+    assert_eq!(crc::key("body"), crc::checksum(b"body"));
+}
+
+#[test]
+fn key_is_const() {
+    const EYES: u32 = crc::key("eyes");
+    assert_eq!(EYES, 1491454825);
+}
+
+#[test]
+fn hasher_matches_checksum() {
+    let bytes: Vec<u8> = (0..=255).cycle().take(1000).collect();
+
+    let mut hasher = crc::Hasher::new();
+    for chunk in bytes.chunks(77) {
+        hasher.update(chunk);
+    }
+
+    assert_eq!(hasher.finalize(), crc::checksum(&bytes));
+}
@@ -0,0 +1,222 @@
+use std::{fmt, io::Write, str::FromStr};
+
+use cascade_save as save;
+use serde::Serialize;
+
+use crate::{Error, Id, Result, Save, Structure, Symbol, Value};
+
+/// A text format a [`Save`] dump can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ron,
+    Json,
+    /// The header, then one line per top-level symbol of the summary and data.
+    JsonLines,
+    Toml,
+    /// An indented, human-readable view. Can not be read back.
+    Tree,
+}
+
+impl Format {
+    pub const ALL: [Format; 5] = [
+        Format::Ron,
+        Format::Json,
+        Format::JsonLines,
+        Format::Toml,
+        Format::Tree,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Ron => "ron",
+            Format::Json => "json",
+            Format::JsonLines => "jsonl",
+            Format::Toml => "toml",
+            Format::Tree => "tree",
+        }
+    }
+
+    /// Guesses the format from a file extension, e.g. `json`.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "txt" => Some(Format::Tree),
+            extension => Self::from_str(extension).ok(),
+        }
+    }
+
+    pub fn write(&self, dump: &Save, writer: &mut impl Write) -> Result<()> {
+        match self {
+            Format::Ron => {
+                let contents = ron::ser::to_string_pretty(dump, ron::ser::PrettyConfig::new())
+                    .map_err(|err| Error::Format(err.to_string()))?;
+                writeln!(writer, "{}", contents)?;
+            }
+            Format::Json => {
+                serde_json::to_writer_pretty(&mut *writer, dump)
+                    .map_err(|err| Error::Format(err.to_string()))?;
+                writeln!(writer)?;
+            }
+            Format::JsonLines => write_json_lines(dump, writer)?,
+            Format::Toml => {
+                let contents =
+                    toml::to_string_pretty(dump).map_err(|err| Error::Format(err.to_string()))?;
+                write!(writer, "{}", contents)?;
+            }
+            Format::Tree => write_tree(dump, writer)?,
+        }
+
+        Ok(())
+    }
+
+    /// Parses a dump written in this format.
+    pub fn parse(&self, contents: &str) -> Result<Save> {
+        match self {
+            Format::Ron => ron::from_str(contents).map_err(|err| Error::Format(err.to_string())),
+            Format::Json => {
+                serde_json::from_str(contents).map_err(|err| Error::Format(err.to_string()))
+            }
+            Format::Toml => toml::from_str(contents).map_err(|err| Error::Format(err.to_string())),
+            Format::JsonLines | Format::Tree => Err(Error::Unreadable(*self)),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.name() == s)
+            .ok_or_else(|| Error::UnknownFormat(s.to_string()))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Line<'a> {
+    Header(&'a save::Header),
+    Summary(&'a Symbol),
+    Data(&'a Symbol),
+}
+
+fn write_json_lines(dump: &Save, writer: &mut impl Write) -> Result<()> {
+    let lines = std::iter::once(Line::Header(&dump.header))
+        .chain(dump.summary.0.iter().map(Line::Summary))
+        .chain(dump.data.0.iter().map(Line::Data));
+
+    for line in lines {
+        serde_json::to_writer(&mut *writer, &line).map_err(|err| Error::Format(err.to_string()))?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) const INDENT: &str = "  ";
+
+fn write_tree(dump: &Save, writer: &mut impl Write) -> Result<()> {
+    let header = &dump.header;
+
+    writeln!(writer, "header")?;
+    writeln!(writer, "{}checksum: {:#010x}", INDENT, header.checksum)?;
+    writeln!(
+        writer,
+        "{}summary_checksum: {:#010x}",
+        INDENT, header.summary_checksum
+    )?;
+    writeln!(writer, "{}summary_size: {}", INDENT, header.summary_size)?;
+    writeln!(writer, "{}total_size: {}", INDENT, header.total_size)?;
+    writeln!(writer, "{}version: {}", INDENT, header.version)?;
+
+    writeln!(writer, "summary")?;
+    write_structure(&dump.summary, 1, writer)?;
+    writeln!(writer, "data")?;
+    write_structure(&dump.data, 1, writer)?;
+
+    Ok(())
+}
+
+pub(crate) fn id_string(id: &Id) -> String {
+    match id {
+        Id::None => "_".to_string(),
+        Id::Checksum(v) => format!("#{}", v),
+        Id::Compress8(v) => format!("@c8:{}", v),
+        Id::Compress16(v) => format!("@c16:{}", v),
+    }
+}
+
+fn write_structure(structure: &Structure, depth: usize, writer: &mut impl Write) -> Result<()> {
+    for symbol in &structure.0 {
+        let label = match &symbol.name {
+            Some(name) => format!("{} ({})", name, id_string(&symbol.id)),
+            None => id_string(&symbol.id),
+        };
+        write_value(&label, &symbol.value, depth, writer)?;
+    }
+
+    Ok(())
+}
+
+/// The text of a value that fits on one line, or `None` for structures and arrays.
+pub(crate) fn scalar(value: &Value) -> Option<String> {
+    let key = |key: &crate::Key| match &key.name {
+        Some(name) => format!("{} (#{})", name, key.checksum),
+        None => format!("#{}", key.checksum),
+    };
+
+    Some(match value {
+        Value::None => "none".to_string(),
+        Value::U8(v) => v.to_string(),
+        Value::U16(v) => v.to_string(),
+        Value::I8(v) => v.to_string(),
+        Value::I16(v) => v.to_string(),
+        Value::I32(v) => v.to_string(),
+        Value::F32(v) => format!("{:?}", f32::from(v)),
+        Value::ZeroInt => "0".to_string(),
+        Value::ZeroFloat => "0.0".to_string(),
+        Value::String(v) => format!("{:?}", v),
+        Value::LocalString(v) => format!("l{:?}", v),
+        Value::Pair(x, y) => format!("({:?}, {:?})", f32::from(x), f32::from(y)),
+        Value::Vector(x, y, z) => format!(
+            "({:?}, {:?}, {:?})",
+            f32::from(x),
+            f32::from(y),
+            f32::from(z)
+        ),
+        Value::Name(v) => key(v),
+        Value::QScript(v) => format!("qscript ({} bytes)", v.len()),
+        Value::CFunction(v) => format!("cfunction {}", key(v)),
+        Value::MemberFunction(v) => format!("member_function {}", key(v)),
+        Value::Structure(_) | Value::StructurePointer(_) | Value::Array(_, _) => return None,
+    })
+}
+
+fn write_value(label: &str, value: &Value, depth: usize, writer: &mut impl Write) -> Result<()> {
+    let indent = INDENT.repeat(depth);
+
+    match value {
+        Value::Structure(structure) | Value::StructurePointer(structure) => {
+            writeln!(writer, "{}{}", indent, label)?;
+            write_structure(structure, depth + 1, writer)?;
+        }
+        Value::Array(_, values) => {
+            writeln!(writer, "{}{} [{}]", indent, label, values.len())?;
+            for (i, value) in values.iter().enumerate() {
+                write_value(&format!("[{}]", i), value, depth + 1, writer)?;
+            }
+        }
+        value => {
+            let scalar = scalar(value).unwrap_or_default();
+            writeln!(writer, "{}{}: {}", indent, label, scalar)?;
+        }
+    }
+
+    Ok(())
+}
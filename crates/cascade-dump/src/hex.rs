@@ -0,0 +1,272 @@
+//! Annotated hex dumps of save files, showing the bytes and offset of every header field and
+//! symbol next to what they decode to.
+
+use std::io::Write;
+
+use cascade_lut::Lut;
+use cascade_qb as qb;
+use cascade_save as save;
+
+use crate::{
+    Result, Value,
+    format::{INDENT, id_string, scalar},
+    id_name,
+};
+
+const BYTES_PER_LINE: usize = 16;
+const HEADER_SIZE: usize = 20;
+const PADDING_BYTE: u8 = 0x69;
+
+const COMPRESS_8_FLAG: u8 = 1 << 7;
+const COMPRESS_16_FLAG: u8 = 1 << 6;
+
+struct Hex<'a, W> {
+    bytes: &'a [u8],
+    lut: &'a Lut,
+    writer: &'a mut W,
+}
+
+/// Writes an annotated hex dump of a save file.
+///
+/// Parsing stops at the first symbol that can not be read, and the error is written in its
+/// place, so damaged saves can be dumped too.
+pub fn write(bytes: &[u8], lut: &Lut, writer: &mut impl Write) -> Result<()> {
+    let mut hex = Hex { bytes, lut, writer };
+
+    let header = match save::Header::read(&mut &bytes[..]) {
+        Ok(header) => header,
+        Err(err) => return hex.error(0, 0, &err.to_string()),
+    };
+
+    hex.line(
+        0,
+        4,
+        0,
+        &format!("header.checksum = {:#010x}", header.checksum),
+    )?;
+    hex.line(
+        4,
+        4,
+        0,
+        &format!(
+            "header.summary_checksum = {:#010x}",
+            header.summary_checksum
+        ),
+    )?;
+    hex.line(
+        8,
+        4,
+        0,
+        &format!("header.summary_size = {}", header.summary_size),
+    )?;
+    hex.line(
+        12,
+        4,
+        0,
+        &format!("header.total_size = {}", header.total_size),
+    )?;
+    hex.line(16, 4, 0, &format!("header.version = {}", header.version))?;
+
+    hex.label(HEADER_SIZE, 0, "summary")?;
+    let Some(offset) = hex.structure(HEADER_SIZE, 1)? else {
+        return Ok(());
+    };
+
+    hex.label(offset, 0, "data")?;
+    let Some(offset) = hex.structure(offset, 1)? else {
+        return Ok(());
+    };
+
+    hex.trailer(offset)
+}
+
+impl<W: Write> Hex<'_, W> {
+    /// Writes `len` bytes from `offset`, with the annotation next to the first line.
+    fn line(&mut self, offset: usize, len: usize, depth: usize, annotation: &str) -> Result<()> {
+        let end = (offset + len).min(self.bytes.len());
+        let chunks = self.bytes[offset..end].chunks(BYTES_PER_LINE);
+
+        for (i, chunk) in chunks.enumerate() {
+            let hex = chunk
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            match i {
+                0 => writeln!(
+                    self.writer,
+                    "{:08x}  {:<width$}  {}{}",
+                    offset,
+                    hex,
+                    INDENT.repeat(depth),
+                    annotation,
+                    width = BYTES_PER_LINE * 3 - 1
+                )?,
+                _ => writeln!(self.writer, "{:08x}  {}", offset + i * BYTES_PER_LINE, hex)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes an annotation without bytes.
+    fn label(&mut self, offset: usize, depth: usize, annotation: &str) -> Result<()> {
+        writeln!(
+            self.writer,
+            "{:08x}  {:<width$}  {}{}",
+            offset,
+            "",
+            INDENT.repeat(depth),
+            annotation,
+            width = BYTES_PER_LINE * 3 - 1
+        )?;
+        Ok(())
+    }
+
+    fn error(&mut self, offset: usize, depth: usize, message: &str) -> Result<()> {
+        self.label(offset, depth, &format!("error: {}", message))
+    }
+
+    /// Writes the symbols of a structure starting at `offset`, returning the offset just past
+    /// its terminator, or `None` if a symbol could not be read.
+    fn structure(&mut self, mut offset: usize, depth: usize) -> Result<Option<usize>> {
+        loop {
+            match self.symbol(offset, depth)? {
+                Some((end, true)) => return Ok(Some(end)),
+                Some((end, false)) => offset = end,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Writes the symbol at `offset`, returning the offset past it and whether it ended its
+    /// structure.
+    fn symbol(&mut self, offset: usize, depth: usize) -> Result<Option<(usize, bool)>> {
+        let mut reader = qb::Reader::new(self.bytes.get(offset..).unwrap_or_default());
+
+        let symbol = match qb::Symbol::read(&mut reader) {
+            Ok(symbol) => symbol,
+            Err(err) => {
+                self.error(offset, depth, &err.to_string())?;
+                return Ok(None);
+            }
+        };
+        let end = offset + reader.offset() as usize;
+
+        let type_byte = self.bytes[offset];
+        if symbol.kind == qb::Kind::None {
+            self.line(offset, 1, depth, "end")?;
+            return Ok(Some((end, true)));
+        }
+
+        let flags = match type_byte & (COMPRESS_8_FLAG | COMPRESS_16_FLAG) {
+            COMPRESS_8_FLAG => ", compress8",
+            COMPRESS_16_FLAG => ", compress16",
+            _ => "",
+        };
+        self.line(
+            offset,
+            1,
+            depth,
+            &format!("type {:?}{}", symbol.kind, flags),
+        )?;
+
+        let id_len = match symbol.id {
+            qb::Id::None => 0,
+            qb::Id::Compress8(_) => 1,
+            qb::Id::Compress16(_) => 2,
+            qb::Id::Checksum(_) => 4,
+        };
+        let id = id_string(&symbol.id.into());
+        let id = match id_name(symbol.id, self.lut) {
+            Some(name) => format!("id {} {}", id, name),
+            None => format!("id {}", id),
+        };
+        self.line(offset + 1, id_len, depth, &id)?;
+
+        match self.value(offset + 1 + id_len, symbol.kind, &symbol.value, depth + 1)? {
+            Some(_) => Ok(Some((end, false))),
+            None => Ok(None),
+        }
+    }
+
+    /// Writes a value whose bytes start at `offset`, returning the offset past it.
+    fn value(
+        &mut self,
+        offset: usize,
+        kind: qb::Kind,
+        value: &qb::Value,
+        depth: usize,
+    ) -> Result<Option<usize>> {
+        match value {
+            qb::Value::Structure(_) | qb::Value::StructurePointer(_) => {
+                self.structure(offset, depth)
+            }
+            qb::Value::Array(element_kind, values) => {
+                self.line(
+                    offset,
+                    3,
+                    depth,
+                    &format!("array of {} {:?}", values.len(), element_kind),
+                )?;
+
+                let mut offset = offset + 3;
+                for (i, element) in values.iter().enumerate() {
+                    self.label(offset, depth, &format!("[{}]", i))?;
+                    match self.value(offset, *element_kind, element, depth + 1)? {
+                        Some(end) => offset = end,
+                        None => return Ok(None),
+                    }
+                }
+
+                Ok(Some(offset))
+            }
+            value => {
+                // Measure the value by reading it back, since strings have no length prefix
+                let mut reader = qb::Reader::new(self.bytes.get(offset..).unwrap_or_default());
+                if let Err(err) = qb::Value::read(&mut reader, kind) {
+                    self.error(offset, depth, &err.to_string())?;
+                    return Ok(None);
+                }
+                let len = reader.offset() as usize;
+
+                let text = scalar(&Value::new(kind, value, self.lut)).unwrap_or_default();
+                match len {
+                    0 => self.label(offset, depth, &format!("value {}", text))?,
+                    len => self.line(offset, len, depth, &format!("value {}", text))?,
+                }
+
+                Ok(Some(offset + len))
+            }
+        }
+    }
+
+    fn trailer(&mut self, offset: usize) -> Result<()> {
+        let trailer = self.bytes.get(offset..).unwrap_or_default();
+        let padding = trailer
+            .iter()
+            .take_while(|byte| **byte == PADDING_BYTE)
+            .count();
+
+        if padding > 0 {
+            self.label(
+                offset,
+                0,
+                &format!("padding: {} bytes of {:#04x}", padding, PADDING_BYTE),
+            )?;
+        }
+
+        let garbage = trailer.len() - padding;
+        if garbage > 0 {
+            self.line(
+                offset + padding,
+                garbage,
+                0,
+                &format!("trailing: {} bytes after padding", garbage),
+            )?;
+        }
+
+        Ok(())
+    }
+}
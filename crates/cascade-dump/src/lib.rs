@@ -1,11 +1,121 @@
+mod format;
+pub mod hex;
+
+use std::{io, result};
+
 use cascade_lut::Lut;
 use cascade_qb as qb;
 use cascade_save as save;
 use encoding_rs::WINDOWS_1252;
+pub use format::Format;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
-pub struct Kind();
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(io::ErrorKind),
+
+    #[error("string can not be encoded as windows-1252: {0}")]
+    Encoding(String),
+
+    #[error("array of {0:?} holds a {1:?} value")]
+    MismatchedArrayKind(qb::Kind, qb::Kind),
+
+    #[error("unknown dump format: {0}")]
+    UnknownFormat(String),
+
+    #[error("{0} dumps can not be read back")]
+    Unreadable(Format),
+
+    #[error("format error: {0}")]
+    Format(String),
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value.kind())
+    }
+}
+
+pub type Result<T, E = Error> = result::Result<T, E>;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum Kind {
+    None,
+    Integer,
+    Float,
+    String,
+    LocalString,
+    Pair,
+    Vector,
+    QScript,
+    CFunction,
+    MemberFunction,
+    Structure,
+    StructurePointer,
+    Array,
+    Name,
+    I8,
+    I16,
+    U8,
+    U16,
+    ZeroInt,
+    ZeroFloat,
+}
+
+impl From<qb::Kind> for Kind {
+    fn from(value: qb::Kind) -> Self {
+        match value {
+            qb::Kind::None => Kind::None,
+            qb::Kind::Integer => Kind::Integer,
+            qb::Kind::Float => Kind::Float,
+            qb::Kind::String => Kind::String,
+            qb::Kind::LocalString => Kind::LocalString,
+            qb::Kind::Pair => Kind::Pair,
+            qb::Kind::Vector => Kind::Vector,
+            qb::Kind::QScript => Kind::QScript,
+            qb::Kind::CFunction => Kind::CFunction,
+            qb::Kind::MemberFunction => Kind::MemberFunction,
+            qb::Kind::Structure => Kind::Structure,
+            qb::Kind::StructurePointer => Kind::StructurePointer,
+            qb::Kind::Array => Kind::Array,
+            qb::Kind::Name => Kind::Name,
+            qb::Kind::I8 => Kind::I8,
+            qb::Kind::I16 => Kind::I16,
+            qb::Kind::U8 => Kind::U8,
+            qb::Kind::U16 => Kind::U16,
+            qb::Kind::ZeroInt => Kind::ZeroInt,
+            qb::Kind::ZeroFloat => Kind::ZeroFloat,
+        }
+    }
+}
+
+impl From<Kind> for qb::Kind {
+    fn from(value: Kind) -> Self {
+        match value {
+            Kind::None => qb::Kind::None,
+            Kind::Integer => qb::Kind::Integer,
+            Kind::Float => qb::Kind::Float,
+            Kind::String => qb::Kind::String,
+            Kind::LocalString => qb::Kind::LocalString,
+            Kind::Pair => qb::Kind::Pair,
+            Kind::Vector => qb::Kind::Vector,
+            Kind::QScript => qb::Kind::QScript,
+            Kind::CFunction => qb::Kind::CFunction,
+            Kind::MemberFunction => qb::Kind::MemberFunction,
+            Kind::Structure => qb::Kind::Structure,
+            Kind::StructurePointer => qb::Kind::StructurePointer,
+            Kind::Array => qb::Kind::Array,
+            Kind::Name => qb::Kind::Name,
+            Kind::I8 => qb::Kind::I8,
+            Kind::I16 => qb::Kind::I16,
+            Kind::U8 => qb::Kind::U8,
+            Kind::U16 => qb::Kind::U16,
+            Kind::ZeroInt => qb::Kind::ZeroInt,
+            Kind::ZeroFloat => qb::Kind::ZeroFloat,
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize)]
 pub enum Id {
@@ -32,6 +142,79 @@ impl From<qb::Id> for Id {
     }
 }
 
+impl From<&Id> for qb::Id {
+    fn from(value: &Id) -> Self {
+        match value {
+            Id::None => qb::Id::None,
+            Id::Checksum(v) => qb::Id::Checksum(*v),
+            Id::Compress8(v) => qb::Id::Compress8(*v),
+            Id::Compress16(v) => qb::Id::Compress16(*v),
+        }
+    }
+}
+
+/// A checksum, with its name if the lut knows it. Only the checksum is used when undumping.
+#[derive(Serialize, Deserialize)]
+pub struct Key {
+    pub checksum: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl Key {
+    fn new(checksum: u32, lut: &Lut) -> Self {
+        Self {
+            checksum,
+            name: lut.checksum.lookup(checksum).cloned(),
+        }
+    }
+}
+
+fn id_name(id: qb::Id, lut: &Lut) -> Option<String> {
+    match id {
+        qb::Id::None => None,
+        qb::Id::Checksum(v) => lut.checksum.lookup(v).cloned(),
+        qb::Id::Compress8(v) => lut.compress.lookup8(v).cloned(),
+        qb::Id::Compress16(v) => lut.compress.lookup16(v).cloned(),
+    }
+}
+
+fn decode(bytes: &[u8]) -> String {
+    let (string, _, _) = WINDOWS_1252.decode(bytes);
+    string.to_string()
+}
+
+fn encode(string: &str) -> Result<Vec<u8>> {
+    match WINDOWS_1252.encode(string) {
+        (_, _, true) => Err(Error::Encoding(string.to_string())),
+        (bytes, _, false) => Ok(bytes.into_owned()),
+    }
+}
+
+/// A float, with its bits so NaNs keep their payload in every format. Only the bits are used
+/// when undumping.
+#[derive(Serialize, Deserialize)]
+pub struct Float {
+    pub bits: u32,
+    #[serde(default, skip_deserializing)]
+    pub value: f32,
+}
+
+impl From<f32> for Float {
+    fn from(value: f32) -> Self {
+        Self {
+            bits: value.to_bits(),
+            value,
+        }
+    }
+}
+
+impl From<&Float> for f32 {
+    fn from(value: &Float) -> Self {
+        f32::from_bits(value.bits)
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub enum Value {
     None,
@@ -40,19 +223,24 @@ pub enum Value {
     I8(i8),
     I16(i16),
     I32(i32),
-    F32(f32),
+    F32(Float),
     ZeroInt,
     ZeroFloat,
     String(String),
-    Pair(f32, f32),
-    Vector(f32, f32, f32),
+    LocalString(String),
+    Pair(Float, Float),
+    Vector(Float, Float, Float),
     Structure(Box<Structure>),
-    Array(Vec<Value>),
-    Name(Option<String>),
+    Array(Kind, Vec<Value>),
+    Name(Key),
+    QScript(Vec<u8>),
+    CFunction(Key),
+    MemberFunction(Key),
+    StructurePointer(Box<Structure>),
 }
 
 impl Value {
-    fn new(value: &qb::Value, lut: &Lut) -> Self {
+    fn new(kind: qb::Kind, value: &qb::Value, lut: &Lut) -> Self {
         match value {
             qb::Value::None => Value::None,
             qb::Value::U8(v) => Value::U8(*v),
@@ -60,24 +248,103 @@ impl Value {
             qb::Value::I8(v) => Value::I8(*v),
             qb::Value::I16(v) => Value::I16(*v),
             qb::Value::I32(v) => Value::I32(*v),
-            qb::Value::F32(v) => Value::F32(*v),
+            qb::Value::F32(v) => Value::F32(Float::from(*v)),
             qb::Value::ZeroInt => Value::ZeroInt,
             qb::Value::ZeroFloat => Value::ZeroFloat,
-            qb::Value::String(v) => {
-                let (name, _, _) = WINDOWS_1252.decode(&v);
-                Value::String(name.to_string())
+            qb::Value::String(v) => match kind {
+                qb::Kind::LocalString => Value::LocalString(decode(v)),
+                _ => Value::String(decode(v)),
+            },
+            qb::Value::Pair(x, y) => Value::Pair(Float::from(*x), Float::from(*y)),
+            qb::Value::Vector(x, y, z) => {
+                Value::Vector(Float::from(*x), Float::from(*y), Float::from(*z))
             }
-            qb::Value::Pair(x, y) => Value::Pair(*x, *y),
-            qb::Value::Vector(x, y, z) => Value::Vector(*x, *y, *z),
             qb::Value::Structure(v) => Value::Structure(Box::new(Structure::new(&v, &lut))),
-            qb::Value::Array(_, v) => {
-                Value::Array(v.iter().map(|symbol| Value::new(&symbol, lut)).collect())
+            qb::Value::Array(kind, v) => Value::Array(
+                (*kind).into(),
+                v.iter()
+                    .map(|value| Value::new(*kind, value, lut))
+                    .collect(),
+            ),
+            qb::Value::Name(v) => Value::Name(Key::new(*v, lut)),
+            qb::Value::QScript(v) => Value::QScript(v.clone()),
+            qb::Value::CFunction(v) => Value::CFunction(Key::new(*v, lut)),
+            qb::Value::MemberFunction(v) => Value::MemberFunction(Key::new(*v, lut)),
+            qb::Value::StructurePointer(v) => {
+                Value::StructurePointer(Box::new(Structure::new(v, lut)))
             }
-            qb::Value::Name(v) => Value::Name(lut.checksum.lookup(*v).cloned()),
+        }
+    }
+
+    /// The kind of symbol or array element this value is written as.
+    pub fn kind(&self) -> qb::Kind {
+        match self {
+            Value::None => qb::Kind::None,
+            Value::U8(_) => qb::Kind::U8,
+            Value::U16(_) => qb::Kind::U16,
+            Value::I8(_) => qb::Kind::I8,
+            Value::I16(_) => qb::Kind::I16,
+            Value::I32(_) => qb::Kind::Integer,
+            Value::F32(_) => qb::Kind::Float,
+            Value::ZeroInt => qb::Kind::ZeroInt,
+            Value::ZeroFloat => qb::Kind::ZeroFloat,
+            Value::String(_) => qb::Kind::String,
+            Value::LocalString(_) => qb::Kind::LocalString,
+            Value::Pair(_, _) => qb::Kind::Pair,
+            Value::Vector(_, _, _) => qb::Kind::Vector,
+            Value::Structure(_) => qb::Kind::Structure,
+            Value::Array(_, _) => qb::Kind::Array,
+            Value::Name(_) => qb::Kind::Name,
+            Value::QScript(_) => qb::Kind::QScript,
+            Value::CFunction(_) => qb::Kind::CFunction,
+            Value::MemberFunction(_) => qb::Kind::MemberFunction,
+            Value::StructurePointer(_) => qb::Kind::StructurePointer,
         }
     }
 }
 
+impl TryFrom<&Value> for qb::Value {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self> {
+        Ok(match value {
+            Value::None => qb::Value::None,
+            Value::U8(v) => qb::Value::U8(*v),
+            Value::U16(v) => qb::Value::U16(*v),
+            Value::I8(v) => qb::Value::I8(*v),
+            Value::I16(v) => qb::Value::I16(*v),
+            Value::I32(v) => qb::Value::I32(*v),
+            Value::F32(v) => qb::Value::F32(v.into()),
+            Value::ZeroInt => qb::Value::ZeroInt,
+            Value::ZeroFloat => qb::Value::ZeroFloat,
+            Value::String(v) | Value::LocalString(v) => qb::Value::String(encode(v)?),
+            Value::Pair(x, y) => qb::Value::Pair(x.into(), y.into()),
+            Value::Vector(x, y, z) => qb::Value::Vector(x.into(), y.into(), z.into()),
+            Value::Structure(v) => qb::Value::Structure(Box::new(v.as_ref().try_into()?)),
+            Value::Array(kind, values) => {
+                let kind = qb::Kind::from(*kind);
+
+                let values = values
+                    .iter()
+                    .map(|value| match value.kind() {
+                        value_kind if value_kind == kind => qb::Value::try_from(value),
+                        value_kind => Err(Error::MismatchedArrayKind(kind, value_kind)),
+                    })
+                    .collect::<Result<_>>()?;
+
+                qb::Value::Array(kind, values)
+            }
+            Value::Name(key) => qb::Value::Name(key.checksum),
+            Value::QScript(v) => qb::Value::QScript(v.clone()),
+            Value::CFunction(key) => qb::Value::CFunction(key.checksum),
+            Value::MemberFunction(key) => qb::Value::MemberFunction(key.checksum),
+            Value::StructurePointer(v) => {
+                qb::Value::StructurePointer(Box::new(v.as_ref().try_into()?))
+            }
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Symbol {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -89,14 +356,9 @@ pub struct Symbol {
 impl Symbol {
     pub fn new(symbol: &qb::Symbol, lut: &Lut) -> Self {
         Self {
-            name: match symbol.id {
-                qb::Id::None => None,
-                qb::Id::Checksum(v) => lut.checksum.lookup(v).cloned(),
-                qb::Id::Compress8(v) => lut.compress.lookup8(v).cloned(),
-                qb::Id::Compress16(v) => lut.compress.lookup16(v).cloned(),
-            },
+            name: id_name(symbol.id, lut),
             id: symbol.id.into(),
-            value: Value::new(&symbol.value, lut),
+            value: Value::new(symbol.kind, &symbol.value, lut),
         }
     }
 }
@@ -115,6 +377,26 @@ impl Structure {
     }
 }
 
+impl TryFrom<&Symbol> for qb::Symbol {
+    type Error = Error;
+
+    fn try_from(symbol: &Symbol) -> Result<Self> {
+        Ok(qb::Symbol {
+            kind: symbol.value.kind(),
+            id: (&symbol.id).into(),
+            value: (&symbol.value).try_into()?,
+        })
+    }
+}
+
+impl TryFrom<&Structure> for qb::Structure {
+    type Error = Error;
+
+    fn try_from(structure: &Structure) -> Result<Self> {
+        structure.0.iter().map(qb::Symbol::try_from).collect()
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct Save {
     #[allow(dead_code)]
@@ -123,6 +405,8 @@ pub struct Save {
     summary: Structure,
     #[allow(dead_code)]
     data: Structure,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    trailer: Option<Vec<u8>>,
 }
 
 impl Save {
@@ -131,6 +415,22 @@ impl Save {
             header: file.header.clone(),
             summary: Structure::new(&*file.summary, lut),
             data: Structure::new(&*file.data, lut),
+            trailer: file.trailer.clone(),
         }
     }
 }
+
+/// Rebuilds the save a dump was made from. Names are ignored in favour of the ids they were
+/// dumped with.
+impl TryFrom<&Save> for save::Save {
+    type Error = Error;
+
+    fn try_from(dump: &Save) -> Result<Self> {
+        Ok(save::Save {
+            header: dump.header.clone(),
+            summary: Box::new((&dump.summary).try_into()?),
+            data: Box::new((&dump.data).try_into()?),
+            trailer: dump.trailer.clone(),
+        })
+    }
+}
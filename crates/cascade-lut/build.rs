@@ -0,0 +1,28 @@
+use std::{collections::BTreeMap, env, fmt::Write, fs, path::PathBuf};
+
+use cascade_crc as crc;
+
+const CHECKSUM_NAMES_PATH: &str = "../../assets/lut/checksum-names.txt";
+
+// Hashes the known names into the checksum lut, so it never goes stale against the name list
+fn main() {
+    println!("cargo::rerun-if-changed={}", CHECKSUM_NAMES_PATH);
+
+    let names = fs::read_to_string(CHECKSUM_NAMES_PATH).expect("could not read checksum names");
+
+    let checksums: BTreeMap<u32, &str> = names
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|name| (crc::key(name), name))
+        .collect();
+
+    let mut ron = String::from("({\n");
+    for (checksum, name) in checksums {
+        writeln!(ron, "    {}: {:?},", checksum, name).unwrap();
+    }
+    ron.push_str("})\n");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set"));
+    fs::write(out_dir.join("checksum.ron"), ron).expect("could not write checksum lut");
+}
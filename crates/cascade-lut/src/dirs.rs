@@ -0,0 +1,25 @@
+//! Where cascade keeps its user files. The app and the cli both resolve it here, so they read the
+//! same luts and groups.
+
+use std::{env, path::PathBuf};
+
+/// The config file of the app. Putting one next to the executable makes the install portable.
+pub const CONFIG_FILENAME: &str = "cascade.toml";
+
+/// The directory of the executable, if it has a [`CONFIG_FILENAME`] in it.
+pub fn portable_dir() -> Option<PathBuf> {
+    let exe = env::current_exe().ok()?;
+    let dir = exe.parent()?;
+
+    match dir.join(CONFIG_FILENAME).is_file() {
+        true => Some(dir.to_path_buf()),
+        false => None,
+    }
+}
+
+/// The directory cascade keeps its user files in: the portable dir if there is one, otherwise
+/// `%localappdata%/cascade`.
+pub fn cascade_dir() -> Option<PathBuf> {
+    portable_dir()
+        .or_else(|| directories::BaseDirs::new().map(|dirs| dirs.data_local_dir().join("cascade")))
+}
@@ -0,0 +1,197 @@
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use cascade_qb as qb;
+
+use crate::{Checksum, Compress, Error, Lut, Result, dirs};
+
+/// The directory users can drop their own lut files into, e.g. `%localappdata%/cascade/lut`.
+pub fn user_dir() -> Option<PathBuf> {
+    dirs::cascade_dir().map(|dir| dir.join("lut"))
+}
+
+/// Where a layer of a [`Layered`] lut was loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// The tables built into cascade.
+    Embedded,
+    File(PathBuf),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Embedded => write!(f, "embedded"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// A lut merged from a stack of layers, where later layers override earlier ones.
+///
+/// Each name remembers the layer it came from, see [`Layered::source`].
+pub struct Layered {
+    lut: Lut,
+    sources: Vec<Source>,
+    checksum_layers: HashMap<u32, usize>,
+    compress8_layers: Vec<usize>,
+    compress16_layers: Vec<usize>,
+}
+
+impl Default for Layered {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn merge_compress(
+    names: &mut Vec<String>,
+    layers: &mut Vec<usize>,
+    overrides: Vec<String>,
+    layer: usize,
+) {
+    if names.len() < overrides.len() {
+        names.resize(overrides.len(), String::new());
+        layers.resize(overrides.len(), layer);
+    }
+
+    // Empty entries leave earlier layers alone, so a layer can name a single id
+    for (i, name) in overrides.into_iter().enumerate() {
+        if !name.is_empty() {
+            names[i] = name;
+            layers[i] = layer;
+        }
+    }
+}
+
+impl Layered {
+    pub fn new() -> Self {
+        Self {
+            lut: Lut {
+                checksum: Checksum::default(),
+                compress: Compress::default(),
+            },
+            sources: vec![],
+            checksum_layers: HashMap::new(),
+            compress8_layers: vec![],
+            compress16_layers: vec![],
+        }
+    }
+
+    /// Adds a layer on top of the existing ones.
+    pub fn push(&mut self, source: Source, lut: Lut) {
+        let layer = self.sources.len();
+        self.sources.push(source);
+
+        for (checksum, name) in lut.checksum.0 {
+            self.lut.checksum.0.insert(checksum, name);
+            self.checksum_layers.insert(checksum, layer);
+        }
+
+        merge_compress(
+            &mut self.lut.compress.compress8,
+            &mut self.compress8_layers,
+            lut.compress.compress8,
+            layer,
+        );
+        merge_compress(
+            &mut self.lut.compress.compress16,
+            &mut self.compress16_layers,
+            lut.compress.compress16,
+            layer,
+        );
+    }
+
+    /// Adds a lut file as a layer.
+    ///
+    /// `.txt` files list known names one per line, and are hashed into checksums. `.ron` files
+    /// hold either a checksum or a compress table.
+    pub fn push_file(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+
+        let lut = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("txt") => Lut {
+                checksum: Checksum::from_names(
+                    contents
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty() && !line.starts_with('#')),
+                ),
+                compress: Compress::default(),
+            },
+            Some("ron") => match Checksum::from_str(&contents) {
+                Ok(checksum) => Lut {
+                    checksum,
+                    compress: Compress::default(),
+                },
+                Err(_) => Lut {
+                    checksum: Checksum::default(),
+                    compress: Compress::from_str(&contents)?,
+                },
+            },
+            _ => return Err(Error::UnknownFormat(path.display().to_string())),
+        };
+
+        self.push(Source::File(path.to_path_buf()), lut);
+
+        Ok(())
+    }
+
+    /// Adds every lut file in `dir` as a layer, in filename order. Other files are skipped.
+    pub fn push_dir(&mut self, dir: impl AsRef<Path>) -> Result<()> {
+        let mut paths = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                matches!(
+                    path.extension().and_then(|extension| extension.to_str()),
+                    Some("txt" | "ron")
+                )
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        for path in paths {
+            self.push_file(path)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn lut(&self) -> &Lut {
+        &self.lut
+    }
+
+    pub fn into_lut(self) -> Lut {
+        self.lut
+    }
+
+    pub fn sources(&self) -> &[Source] {
+        &self.sources
+    }
+
+    /// The layer that named `id`, if any did.
+    pub fn source(&self, id: qb::Id) -> Option<&Source> {
+        let layer = match id {
+            qb::Id::Checksum(checksum) => self.checksum_layers.get(&checksum).copied(),
+            qb::Id::Compress8(i) => self
+                .lut
+                .compress
+                .lookup8(i)
+                .filter(|name| !name.is_empty())
+                .and(self.compress8_layers.get(i as usize).copied()),
+            qb::Id::Compress16(i) => self
+                .lut
+                .compress
+                .lookup16(i)
+                .filter(|name| !name.is_empty())
+                .and(self.compress16_layers.get(i as usize).copied()),
+            qb::Id::None => None,
+        }?;
+
+        self.sources.get(layer)
+    }
+}
@@ -1,13 +1,23 @@
-use std::{collections::HashMap, result, str::Utf8Error};
+pub mod dirs;
+mod layered;
 
+use std::{collections::HashMap, io, result, str::Utf8Error};
+
+use cascade_crc as crc;
 use cascade_qb as qb;
+pub use dirs::cascade_dir;
+pub use layered::{Layered, Source, user_dir};
 use ron::de::SpannedError;
 use serde::{Deserialize, Serialize};
 
-const CHECKSUM_LUT_BYTES: &[u8] = include_bytes!("../../../assets/lut/checksum.ron");
+// Generated by build.rs from assets/lut/checksum-names.txt
+const CHECKSUM_LUT_BYTES: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/checksum.ron"));
 
 #[derive(thiserror::Error, Debug, Clone)]
 pub enum Error {
+    #[error("io error: {0}")]
+    Io(io::ErrorKind),
+
     #[error("ron deserialization error: {0}")]
     Spanned(#[from] SpannedError),
 
@@ -19,11 +29,20 @@ pub enum Error {
 
     #[error("id not found in LUT: {0:?}")]
     IdNotFound(qb::Id),
+
+    #[error("unknown lut file format: {0}")]
+    UnknownFormat(String),
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value.kind())
+    }
 }
 
 pub type Result<T, E = Error> = result::Result<T, E>;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Checksum(pub HashMap<u32, String>);
 
 impl Checksum {
@@ -36,16 +55,33 @@ impl Checksum {
         Ok(ron::from_str(s)?)
     }
 
+    /// Builds a lut from known names, hashing each one.
+    pub fn from_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Self {
+        Self(
+            names
+                .into_iter()
+                .map(|name| (crc::key(name), name.to_string()))
+                .collect(),
+        )
+    }
+
     pub fn lookup(&self, value: u32) -> Option<&String> {
         self.0.get(&value)
     }
 
+    pub fn lookup_name(&self, name: &str) -> Option<qb::Id> {
+        self.0
+            .iter()
+            .find(|(_, entry)| entry.eq_ignore_ascii_case(name))
+            .map(|(checksum, _)| qb::Id::Checksum(*checksum))
+    }
+
     pub fn load() -> Result<Self> {
         Self::from_bytes(CHECKSUM_LUT_BYTES)
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Compress {
     compress8: Vec<String>,
     compress16: Vec<String>,
@@ -68,9 +104,57 @@ impl Compress {
     pub fn lookup16(&self, value: u16) -> Option<&String> {
         self.compress16.get(value as usize)
     }
+
+    /// Finds the compressed id of `name`, preferring 8-bit ids.
+    pub fn lookup_name(&self, name: &str) -> Option<qb::Id> {
+        let position = |table: &[String]| {
+            table
+                .iter()
+                .position(|entry| entry.eq_ignore_ascii_case(name))
+        };
+
+        position(&self.compress8)
+            .and_then(|i| u8::try_from(i).ok().map(qb::Id::Compress8))
+            .or_else(|| {
+                position(&self.compress16)
+                    .and_then(|i| u16::try_from(i).ok().map(qb::Id::Compress16))
+            })
+    }
+}
+
+impl qb::Resolve for Compress {
+    fn resolve(&self, name: &str) -> Vec<qb::Id> {
+        let compress8 = self
+            .compress8
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.eq_ignore_ascii_case(name))
+            .filter_map(|(i, _)| u8::try_from(i).ok().map(qb::Id::Compress8));
+
+        let compress16 = self
+            .compress16
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.eq_ignore_ascii_case(name))
+            .filter_map(|(i, _)| u16::try_from(i).ok().map(qb::Id::Compress16));
+
+        // Any name can be hashed, so there is no need to search the checksum table
+        let checksum = qb::Id::Checksum(crc::key(name));
+
+        compress8
+            .chain(compress16)
+            .chain(std::iter::once(checksum))
+            .collect()
+    }
 }
 
 pub struct Lut {
     pub checksum: Checksum,
     pub compress: Compress,
 }
+
+impl qb::Resolve for Lut {
+    fn resolve(&self, name: &str) -> Vec<qb::Id> {
+        self.compress.resolve(name)
+    }
+}
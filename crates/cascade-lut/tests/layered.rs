@@ -0,0 +1,73 @@
+use std::{env, fs, path::PathBuf};
+
+use cascade_lut::{self as lut, Layered, Lut, Source};
+use cascade_qb as qb;
+
+fn lut_dir() -> PathBuf {
+    let dir = env::temp_dir().join("cascade").join("tests").join("lut");
+    fs::create_dir_all(&dir).expect("could not create lut dir");
+    dir
+}
+
+#[test]
+fn later_layers_override() {
+    let dir = lut_dir();
+
+    let names = dir.join("names.txt");
+    fs::write(&names, "# extra names\nmy_new_symbol\nTOTAL_SCORE\n")
+        .expect("could not write names");
+
+    let compress = dir.join("compress.ron");
+    fs::write(
+        &compress,
+        r#"(compress8: ["", "socks_override"], compress16: [])"#,
+    )
+    .expect("could not write compress lut");
+
+    let mut layered = Layered::new();
+    layered.push(
+        Source::Embedded,
+        Lut {
+            checksum: lut::Checksum::load().expect("could not load checksum lut"),
+            compress: lut::Compress::from_str(r#"(compress8: ["hat", "socks"], compress16: [])"#)
+                .expect("could not parse compress lut"),
+        },
+    );
+    layered.push_file(&names).expect("could not load names");
+    layered
+        .push_file(&compress)
+        .expect("could not load compress lut");
+
+    let lut = layered.lut();
+
+    assert_eq!(
+        lut.checksum.lookup(236215964).map(String::as_str),
+        Some("TOTAL_SCORE")
+    );
+    assert_eq!(
+        layered.source(qb::Id::Checksum(236215964)),
+        Some(&Source::File(names.clone()))
+    );
+
+    // Untouched by later layers
+    assert_eq!(
+        layered.source(qb::Id::Checksum(1431076207)),
+        Some(&Source::Embedded)
+    );
+
+    assert_eq!(lut.compress.lookup8(0).map(String::as_str), Some("hat"));
+    assert_eq!(
+        layered.source(qb::Id::Compress8(0)),
+        Some(&Source::Embedded)
+    );
+    assert_eq!(
+        lut.compress.lookup8(1).map(String::as_str),
+        Some("socks_override")
+    );
+    assert_eq!(
+        layered.source(qb::Id::Compress8(1)),
+        Some(&Source::File(compress))
+    );
+
+    assert_eq!(layered.source(qb::Id::Checksum(1)), None);
+}
@@ -0,0 +1,42 @@
+use cascade_lut as lut;
+use cascade_qb as qb;
+
+#[test]
+fn embedded_checksum_lut() {
+    let checksum = lut::Checksum::load().expect("could not load checksum lut");
+
+    assert_eq!(
+        checksum.lookup(236215964).map(String::as_str),
+        Some("total_score")
+    );
+    assert_eq!(
+        checksum.lookup_name("Total_Score"),
+        Some(qb::Id::Checksum(236215964))
+    );
+    assert_eq!(checksum.lookup_name("not_a_known_name"), None);
+}
+
+#[test]
+fn checksum_from_names() {
+    let checksum = lut::Checksum::from_names(["appearance", "eyes"]);
+
+    assert_eq!(
+        checksum.lookup(1431076207).map(String::as_str),
+        Some("appearance")
+    );
+    assert_eq!(
+        checksum.lookup_name("eyes"),
+        Some(qb::Id::Checksum(1491454825))
+    );
+}
+
+#[test]
+fn compress_lookup_name() {
+    let compress =
+        lut::Compress::from_str(r#"(compress8: ["hat", "socks"], compress16: ["socks", "tie"])"#)
+            .expect("could not parse compress lut");
+
+    assert_eq!(compress.lookup_name("SOCKS"), Some(qb::Id::Compress8(1)));
+    assert_eq!(compress.lookup_name("tie"), Some(qb::Id::Compress16(1)));
+    assert_eq!(compress.lookup_name("shoes"), None);
+}
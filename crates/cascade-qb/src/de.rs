@@ -0,0 +1,448 @@
+use std::fmt::Display;
+
+use cascade_crc as crc;
+use serde::de::{
+    self,
+    value::{BorrowedStrDeserializer, SeqDeserializer},
+    Deserialize, DeserializeSeed, IntoDeserializer, Visitor,
+};
+
+use crate::{text, Error, Id, Resolve, Result, Structure, Symbol, Value};
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Serde(msg.to_string())
+    }
+}
+
+/// Deserializes a `T` from `structure`, the inverse of [`to_structure`](crate::to_structure).
+///
+/// A struct field matches a symbol whose id `names` resolves the field name to, or whose id is
+/// the checksum of the field name. Symbols no field matches are ignored.
+pub fn from_structure<'a, T: Deserialize<'a>>(
+    structure: &'a Structure,
+    names: &'a impl Resolve,
+) -> Result<T> {
+    T::deserialize(StructureDeserializer { structure, names })
+}
+
+fn matches(symbol: &Symbol, name: &str, names: &impl Resolve) -> bool {
+    symbol.id == Id::Checksum(crc::key(name)) || names.resolve(name).contains(&symbol.id)
+}
+
+fn id_string(id: Id) -> String {
+    let mut out = String::new();
+    text::write_id(&mut out, id);
+    out
+}
+
+struct StructureDeserializer<'a, N> {
+    structure: &'a Structure,
+    names: &'a N,
+}
+
+impl<'de, N: Resolve> de::Deserializer<'de> for StructureDeserializer<'de, N> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_map(StructureAccess {
+            symbols: self.structure.iter(),
+            value: None,
+            names: self.names,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_map(FieldAccess {
+            structure: self.structure,
+            fields: fields.iter(),
+            value: None,
+            names: self.names,
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let mut symbols = self.structure.iter();
+
+        let (Some(symbol), None) = (symbols.next(), symbols.next()) else {
+            return Err(Error::Serde(
+                "an enum variant with content must be a structure with one symbol".to_string(),
+            ));
+        };
+
+        let variant = variants
+            .iter()
+            .find(|variant| matches(symbol, variant, self.names))
+            .copied()
+            .ok_or_else(|| Error::Serde(format!("unknown variant {}", id_string(symbol.id))))?;
+
+        visitor.visit_enum(EnumAccess {
+            variant,
+            value: Some(&symbol.value),
+            names: self.names,
+        })
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map identifier ignored_any
+    }
+}
+
+struct ValueDeserializer<'a, N> {
+    value: &'a Value,
+    names: &'a N,
+}
+
+impl<'de, N: Resolve> ValueDeserializer<'de, N> {
+    fn integer(&self) -> Option<i64> {
+        match *self.value {
+            Value::U8(v) => Some(v as i64),
+            Value::U16(v) => Some(v as i64),
+            Value::I8(v) => Some(v as i64),
+            Value::I16(v) => Some(v as i64),
+            Value::I32(v) => Some(v as i64),
+            Value::ZeroInt => Some(0),
+            _ => None,
+        }
+    }
+
+    fn seq<V: Visitor<'de>>(&self, values: &'de [Value], visitor: V) -> Result<V::Value> {
+        let names = self.names;
+        let mut seq = SeqDeserializer::new(
+            values
+                .iter()
+                .map(|value| ValueDeserializer { value, names }),
+        );
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+
+    fn floats<V: Visitor<'de>>(&self, floats: &[f32], visitor: V) -> Result<V::Value> {
+        let mut seq = SeqDeserializer::<_, Error>::new(floats.iter().copied());
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+}
+
+impl<'de, N: Resolve> IntoDeserializer<'de, Error> for ValueDeserializer<'de, N> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de, N: Resolve> de::Deserializer<'de> for ValueDeserializer<'de, N> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let names = self.names;
+
+        match self.value {
+            Value::None => visitor.visit_unit(),
+            Value::U8(v) => visitor.visit_u8(*v),
+            Value::U16(v) => visitor.visit_u16(*v),
+            Value::I8(v) => visitor.visit_i8(*v),
+            Value::I16(v) => visitor.visit_i16(*v),
+            Value::I32(v) => visitor.visit_i32(*v),
+            Value::F32(v) => visitor.visit_f32(*v),
+            Value::ZeroInt => visitor.visit_i32(0),
+            Value::ZeroFloat => visitor.visit_f32(0.),
+            Value::String(bytes) => match std::str::from_utf8(bytes) {
+                Ok(string) => visitor.visit_borrowed_str(string),
+                Err(_) => visitor.visit_borrowed_bytes(bytes),
+            },
+            Value::Pair(a, b) => self.floats(&[*a, *b], visitor),
+            Value::Vector(a, b, c) => self.floats(&[*a, *b, *c], visitor),
+            Value::Structure(structure) | Value::StructurePointer(structure) => {
+                StructureDeserializer { structure, names }.deserialize_any(visitor)
+            }
+            Value::Array(_, values) => self.seq(values, visitor),
+            Value::Name(v) | Value::CFunction(v) | Value::MemberFunction(v) => {
+                visitor.visit_u32(*v)
+            }
+            Value::QScript(bytes) => visitor.visit_borrowed_bytes(bytes),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.integer() {
+            Some(v) => visitor.visit_bool(v != 0),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.integer() {
+            Some(v) => visitor.visit_f32(v as f32),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_f32(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::None => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.value {
+            Value::Structure(structure) | Value::StructurePointer(structure) => {
+                StructureDeserializer {
+                    structure,
+                    names: self.names,
+                }
+                .deserialize_struct(name, fields, visitor)
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let variant = match self.value {
+            Value::Structure(structure) | Value::StructurePointer(structure) => {
+                return StructureDeserializer {
+                    structure,
+                    names: self.names,
+                }
+                .deserialize_enum(name, variants, visitor);
+            }
+            Value::Name(checksum) => variants
+                .iter()
+                .find(|variant| crc::key(variant) == *checksum)
+                .copied()
+                .ok_or_else(|| Error::Serde(format!("unknown variant #{}", checksum)))?,
+            Value::String(bytes) => variants
+                .iter()
+                .find(|variant| variant.as_bytes().eq_ignore_ascii_case(bytes))
+                .copied()
+                .ok_or_else(|| {
+                    Error::Serde(format!(
+                        "unknown variant \"{}\"",
+                        String::from_utf8_lossy(bytes)
+                    ))
+                })?,
+            value => {
+                return Err(Error::Serde(format!(
+                    "expected an enum variant, got {}",
+                    value
+                )))
+            }
+        };
+
+        visitor.visit_enum(EnumAccess {
+            variant,
+            value: None,
+            names: self.names,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char str string bytes byte_buf unit unit_struct
+        seq tuple tuple_struct map identifier ignored_any
+    }
+}
+
+/// Yields every symbol of a structure, keyed by its id.
+struct StructureAccess<'a, I, N> {
+    symbols: I,
+    value: Option<&'a Value>,
+    names: &'a N,
+}
+
+impl<'de, I: Iterator<Item = &'de Symbol>, N: Resolve> de::MapAccess<'de>
+    for StructureAccess<'de, I, N>
+{
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        let Some(symbol) = self.symbols.next() else {
+            return Ok(None);
+        };
+
+        self.value = Some(&symbol.value);
+        seed.deserialize(IdDeserializer(symbol.id)).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::Serde("map value without a key".to_string()))?;
+
+        seed.deserialize(ValueDeserializer {
+            value,
+            names: self.names,
+        })
+    }
+}
+
+/// Yields the symbols of a structure that match the fields of a struct, keyed by field name.
+struct FieldAccess<'a, N> {
+    structure: &'a Structure,
+    fields: std::slice::Iter<'static, &'static str>,
+    value: Option<&'a Value>,
+    names: &'a N,
+}
+
+impl<'de, N: Resolve> de::MapAccess<'de> for FieldAccess<'de, N> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        for field in self.fields.by_ref() {
+            let symbol = self
+                .structure
+                .iter()
+                .find(|symbol| matches(symbol, field, self.names));
+
+            if let Some(symbol) = symbol {
+                self.value = Some(&symbol.value);
+                return seed
+                    .deserialize(BorrowedStrDeserializer::new(field))
+                    .map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::Serde("struct field without a name".to_string()))?;
+
+        seed.deserialize(ValueDeserializer {
+            value,
+            names: self.names,
+        })
+    }
+}
+
+/// Deserializes a map key: checksums as integers, or any id as its text form (`#123`, `@c8:4`).
+struct IdDeserializer(Id);
+
+impl<'de> de::Deserializer<'de> for IdDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_string(id_string(self.0))
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.0 {
+            Id::Checksum(checksum) => visitor.visit_u32(checksum),
+            id => visitor.visit_string(id_string(id)),
+        }
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_u32(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u128 f32 f64 char str string bytes byte_buf option unit
+        unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct EnumAccess<'a, N> {
+    variant: &'static str,
+    value: Option<&'a Value>,
+    names: &'a N,
+}
+
+impl<'de, N: Resolve> de::EnumAccess<'de> for EnumAccess<'de, N> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let variant = seed.deserialize(BorrowedStrDeserializer::<Error>::new(self.variant))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, N: Resolve> de::VariantAccess<'de> for EnumAccess<'de, N> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self.content()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_any(self.content()?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_struct(self.content()?, self.variant, fields, visitor)
+    }
+}
+
+impl<'de, N: Resolve> EnumAccess<'de, N> {
+    fn content(&self) -> Result<ValueDeserializer<'de, N>> {
+        let value = self.value.ok_or_else(|| {
+            Error::Serde(format!("variant {} is missing its content", self.variant))
+        })?;
+
+        Ok(ValueDeserializer {
+            value,
+            names: self.names,
+        })
+    }
+}
@@ -0,0 +1,299 @@
+use std::{collections::HashSet, fmt};
+
+use crate::{text, Error, Kind, Path, Result, Segment, Structure, Symbol, Value};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Change {
+    /// A symbol or array element only present in the new structure.
+    Added {
+        path: Path,
+        kind: Kind,
+        value: Value,
+    },
+    /// A symbol or array element only present in the old structure.
+    Removed {
+        path: Path,
+        kind: Kind,
+        value: Value,
+    },
+    /// A symbol or array element present in both, with a different kind or value.
+    Changed {
+        path: Path,
+        old_kind: Kind,
+        old: Value,
+        kind: Kind,
+        value: Value,
+    },
+}
+
+impl Change {
+    pub fn path(&self) -> &Path {
+        match self {
+            Change::Added { path, .. }
+            | Change::Removed { path, .. }
+            | Change::Changed { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value_string = |kind: Kind, value: &Value| {
+            let mut out = String::new();
+            text::write_value(&mut out, kind, value, 0);
+            out
+        };
+
+        match self {
+            Change::Added { path, kind, value } => {
+                write!(f, "+ {} = {}", path, value_string(*kind, value))
+            }
+            Change::Removed { path, kind, value } => {
+                write!(f, "- {} = {}", path, value_string(*kind, value))
+            }
+            Change::Changed {
+                path,
+                old_kind,
+                old,
+                kind,
+                value,
+            } => write!(
+                f,
+                "~ {} = {} -> {}",
+                path,
+                value_string(*old_kind, old),
+                value_string(*kind, value)
+            ),
+        }
+    }
+}
+
+/// The changes between two structures, in the order they should be applied.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Patch(Vec<Change>);
+
+impl Patch {
+    pub fn new(changes: Vec<Change>) -> Self {
+        Self(changes)
+    }
+
+    pub fn changes(&self) -> &[Change] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Replays the patch onto `structure`.
+    ///
+    /// Changed symbols that are missing from `structure` are added, and removed symbols that are
+    /// already missing are skipped. The parent of every change must exist.
+    pub fn apply(&self, structure: &mut Structure) -> Result<()> {
+        for change in &self.0 {
+            match change {
+                Change::Added { path, kind, value } => {
+                    put(structure, path, *kind, value.clone(), true)?
+                }
+                Change::Changed {
+                    path, kind, value, ..
+                } => put(structure, path, *kind, value.clone(), false)?,
+                Change::Removed { path, .. } => {
+                    structure.remove_path(path, &());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Patch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.0 {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+fn put(
+    structure: &mut Structure,
+    path: &Path,
+    kind: Kind,
+    value: Value,
+    insert: bool,
+) -> Result<()> {
+    let not_found = || Error::PathNotFound(path.to_string());
+
+    let Some((last, parent)) = path.segments().split_last() else {
+        // An empty path stands for the root structure itself
+        *structure = *value.try_as_structure()?;
+        return Ok(());
+    };
+
+    let parent = match parent.is_empty() {
+        true => structure,
+        false => match structure.get_path_mut(&Path::new(parent.to_vec()), &()) {
+            Some(Value::Array(_, values)) => {
+                return match *last {
+                    Segment::Index(index) if insert && index <= values.len() => {
+                        values.insert(index, value);
+                        Ok(())
+                    }
+                    Segment::Index(index) if index < values.len() => {
+                        values[index] = value;
+                        Ok(())
+                    }
+                    _ => Err(not_found()),
+                };
+            }
+            Some(Value::Structure(parent) | Value::StructurePointer(parent)) => parent,
+            _ => return Err(not_found()),
+        },
+    };
+
+    match *last {
+        Segment::Id(id) => {
+            parent.insert(Symbol { kind, id, value });
+            Ok(())
+        }
+        _ => Err(not_found()),
+    }
+}
+
+fn same(old_kind: Kind, old: &Value, new_kind: Kind, new: &Value) -> bool {
+    let mut old_bytes = vec![];
+    let mut new_bytes = vec![];
+
+    old_kind == new_kind
+        && old.write(&mut old_bytes).is_ok()
+        && new.write(&mut new_bytes).is_ok()
+        && old_bytes == new_bytes
+}
+
+fn has_duplicate_ids(structure: &Structure) -> bool {
+    let mut seen = HashSet::new();
+    structure.iter().any(|symbol| !seen.insert(symbol.id))
+}
+
+fn diff_value(
+    changes: &mut Vec<Change>,
+    path: Path,
+    old_kind: Kind,
+    old: &Value,
+    kind: Kind,
+    value: &Value,
+) {
+    if same(old_kind, old, kind, value) {
+        return;
+    }
+
+    match (old, value) {
+        (Value::Structure(old), Value::Structure(new))
+        | (Value::StructurePointer(old), Value::StructurePointer(new))
+            if !has_duplicate_ids(old) && !has_duplicate_ids(new) =>
+        {
+            diff_structure(changes, path, old, new)
+        }
+        (Value::Array(old_element_kind, old), Value::Array(element_kind, new))
+            if old_element_kind == element_kind =>
+        {
+            let element_kind = *element_kind;
+
+            for (index, (old, new)) in old.iter().zip(new.iter()).enumerate() {
+                diff_value(
+                    changes,
+                    path.join(Segment::Index(index)),
+                    element_kind,
+                    old,
+                    element_kind,
+                    new,
+                );
+            }
+
+            for (index, value) in new.iter().enumerate().skip(old.len()) {
+                changes.push(Change::Added {
+                    path: path.join(Segment::Index(index)),
+                    kind: element_kind,
+                    value: value.clone(),
+                });
+            }
+
+            // Remove from the back so earlier indices stay valid
+            for (index, value) in old.iter().enumerate().skip(new.len()).rev() {
+                changes.push(Change::Removed {
+                    path: path.join(Segment::Index(index)),
+                    kind: element_kind,
+                    value: value.clone(),
+                });
+            }
+        }
+        _ => changes.push(Change::Changed {
+            path,
+            old_kind,
+            old: old.clone(),
+            kind,
+            value: value.clone(),
+        }),
+    }
+}
+
+fn diff_structure(changes: &mut Vec<Change>, path: Path, old: &Structure, new: &Structure) {
+    for symbol in old.iter() {
+        let symbol_path = path.join(Segment::Id(symbol.id));
+
+        match new.get(symbol.id) {
+            Some(new_symbol) => diff_value(
+                changes,
+                symbol_path,
+                symbol.kind,
+                &symbol.value,
+                new_symbol.kind,
+                &new_symbol.value,
+            ),
+            None => changes.push(Change::Removed {
+                path: symbol_path,
+                kind: symbol.kind,
+                value: symbol.value.clone(),
+            }),
+        }
+    }
+
+    for symbol in new.iter().filter(|symbol| old.get(symbol.id).is_none()) {
+        changes.push(Change::Added {
+            path: path.join(Segment::Id(symbol.id)),
+            kind: symbol.kind,
+            value: symbol.value.clone(),
+        });
+    }
+}
+
+/// Computes the changes that turn `old` into `new`.
+///
+/// Symbols are matched by id, and array elements by index. Structures that repeat an id are
+/// compared as a whole. Symbol order is not tracked.
+pub fn diff(old: &Structure, new: &Structure) -> Patch {
+    let mut changes = vec![];
+
+    if !has_duplicate_ids(old) && !has_duplicate_ids(new) {
+        diff_structure(&mut changes, Path::default(), old, new);
+    } else if old.raw_bytes().ok() != new.raw_bytes().ok() {
+        // An empty path replaces the whole root
+        changes.push(Change::Changed {
+            path: Path::default(),
+            old_kind: Kind::Structure,
+            old: Value::Structure(Box::new(old.clone())),
+            kind: Kind::Structure,
+            value: Value::Structure(Box::new(new.clone())),
+        });
+    }
+
+    Patch(changes)
+}
@@ -1,6 +1,6 @@
 use std::{fmt::Debug, io, result};
 
-use crate::Value;
+use crate::{Path, Value};
 
 #[derive(thiserror::Error, Debug, Clone)]
 pub enum Error {
@@ -18,6 +18,47 @@ pub enum Error {
 
     #[error("expected value type {0}, got {1}")]
     ExpectedValueType(String, Value),
+
+    #[error("invalid path \"{0}\": {1}")]
+    InvalidPath(String, String),
+
+    #[error("path not found: {0}")]
+    PathNotFound(String),
+
+    #[error("could not resolve name \"{0}\" to an id")]
+    UnresolvedName(String),
+
+    #[error("parse error at line {line}, column {column}: {message}")]
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    #[error("serde error: {0}")]
+    Serde(String),
+
+    /// A read error, with the offset of the symbol or array element that failed and its path.
+    #[error("at offset {offset:#x}, symbol \"{path}\": {source}")]
+    At {
+        offset: u64,
+        path: Path,
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Attaches read context, unless a nested read already did.
+    pub(crate) fn at(self, offset: u64, path: &Path) -> Self {
+        match self {
+            Error::At { .. } => self,
+            source => Error::At {
+                offset,
+                path: path.clone(),
+                source: Box::new(source),
+            },
+        }
+    }
 }
 
 impl From<io::Error> for Error {
@@ -1,14 +1,29 @@
+#[cfg(feature = "serde")]
+mod de;
+mod diff;
 mod error;
 mod id;
 mod kind;
+mod path;
+mod reader;
 mod save;
+#[cfg(feature = "serde")]
+mod ser;
 mod structure;
 mod symbol;
+pub mod text;
 mod value;
 
+#[cfg(feature = "serde")]
+pub use de::from_structure;
+pub use diff::{diff, Change, Patch};
 pub use error::{Error, Result};
 pub use id::Id;
 pub use kind::Kind;
+pub use path::{Path, Resolve, Segment};
+pub use reader::Reader;
+#[cfg(feature = "serde")]
+pub use ser::to_structure;
 pub use structure::Structure;
 pub use symbol::Symbol;
 pub use value::Value;
@@ -0,0 +1,341 @@
+use std::{fmt, str::FromStr};
+
+use crate::{text, Error, Id, Kind, Result, Structure, Symbol, Value};
+
+/// Maps symbol names to the ids they may be stored under.
+pub trait Resolve {
+    /// Returns every id that may stand for `name`, most preferred first.
+    ///
+    /// The first id is used when a path creates a new symbol.
+    fn resolve(&self, name: &str) -> Vec<Id>;
+}
+
+/// Resolves nothing, so only raw ids can be used in paths.
+impl Resolve for () {
+    fn resolve(&self, _name: &str) -> Vec<Id> {
+        vec![]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Segment {
+    Id(Id),
+    Name(String),
+    Index(usize),
+}
+
+impl Segment {
+    /// The ids a symbol at this segment may have, or `None` if it indexes an array.
+    fn ids(&self, names: &impl Resolve) -> Option<Vec<Id>> {
+        match self {
+            Segment::Id(id) => Some(vec![*id]),
+            Segment::Name(name) => Some(names.resolve(name)),
+            Segment::Index(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Segment::Id(id) => {
+                let mut out = String::new();
+                text::write_id(&mut out, *id);
+                write!(f, "{}", out)
+            }
+            Segment::Name(name) => write!(f, "{}", name),
+            Segment::Index(index) => write!(f, "[{}]", index),
+        }
+    }
+}
+
+/// A dotted path to a value inside a structure, e.g. `custom_skater.custom.appearance.hat`,
+/// `#314551426.@c8:195` or `trick_mapping[2]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Path(Vec<Segment>);
+
+impl Path {
+    pub fn new(segments: Vec<Segment>) -> Self {
+        Self(segments)
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn join(&self, segment: Segment) -> Self {
+        let mut segments = self.0.clone();
+        segments.push(segment);
+        Self(segments)
+    }
+
+    pub fn parent(&self) -> Option<Path> {
+        self.0.split_last().map(|(_, parent)| Self(parent.to_vec()))
+    }
+
+    pub fn last(&self) -> Option<&Segment> {
+        self.0.last()
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                Segment::Index(_) => (),
+                _ if i > 0 => write!(f, ".")?,
+                _ => (),
+            }
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn parse_id(s: &str) -> Option<Id> {
+    fn number<T: FromStr + TryFrom<u64>>(s: &str) -> Option<T> {
+        match s.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16)
+                .ok()
+                .and_then(|v| T::try_from(v).ok()),
+            None => s.parse().ok(),
+        }
+    }
+
+    if let Some(checksum) = s.strip_prefix('#') {
+        number(checksum).map(Id::Checksum)
+    } else if let Some(index) = s.strip_prefix("@c8:") {
+        number(index).map(Id::Compress8)
+    } else if let Some(index) = s.strip_prefix("@c16:") {
+        number(index).map(Id::Compress16)
+    } else {
+        None
+    }
+}
+
+impl FromStr for Path {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |reason: &str| Error::InvalidPath(s.to_string(), reason.to_string());
+        let mut segments = vec![];
+
+        for part in s.split('.') {
+            let (key, mut indices) = match part.find('[') {
+                Some(i) => part.split_at(i),
+                None => (part, ""),
+            };
+
+            segments.push(if key.starts_with(['#', '@']) {
+                Segment::Id(parse_id(key).ok_or_else(|| invalid("malformed id"))?)
+            } else if key.is_empty() {
+                return Err(invalid("empty segment"));
+            } else {
+                Segment::Name(key.to_string())
+            });
+
+            while !indices.is_empty() {
+                let end = indices.find(']').ok_or_else(|| invalid("unclosed '['"))?;
+                let index = indices[1..end]
+                    .parse()
+                    .map_err(|_| invalid("array index must be a number"))?;
+
+                segments.push(Segment::Index(index));
+                indices = &indices[end + 1..];
+
+                if !indices.is_empty() && !indices.starts_with('[') {
+                    return Err(invalid("expected '.' or '[' after array index"));
+                }
+            }
+        }
+
+        Ok(Self(segments))
+    }
+}
+
+impl TryFrom<&str> for Path {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        Self::from_str(value)
+    }
+}
+
+fn child<'a>(value: &'a Value, segment: &Segment, names: &impl Resolve) -> Option<&'a Value> {
+    match (value, segment) {
+        (Value::Array(_, values), Segment::Index(index)) => values.get(*index),
+        (_, Segment::Index(_)) => None,
+        (Value::Structure(structure) | Value::StructurePointer(structure), key) => {
+            structure.find(key, names).map(|symbol| &symbol.value)
+        }
+        _ => None,
+    }
+}
+
+fn child_mut<'a>(
+    value: &'a mut Value,
+    segment: &Segment,
+    names: &impl Resolve,
+) -> Option<&'a mut Value> {
+    match (value, segment) {
+        (Value::Array(_, values), Segment::Index(index)) => values.get_mut(*index),
+        (_, Segment::Index(_)) => None,
+        (Value::Structure(structure) | Value::StructurePointer(structure), key) => structure
+            .find_mut(key, names)
+            .map(|symbol| &mut symbol.value),
+        _ => None,
+    }
+}
+
+impl Structure {
+    fn find(&self, segment: &Segment, names: &impl Resolve) -> Option<&Symbol> {
+        let ids = segment.ids(names)?;
+        self.iter().find(|symbol| ids.contains(&symbol.id))
+    }
+
+    fn find_mut(&mut self, segment: &Segment, names: &impl Resolve) -> Option<&mut Symbol> {
+        let ids = segment.ids(names)?;
+        self.iter_mut().find(|symbol| ids.contains(&symbol.id))
+    }
+
+    pub fn get_path(&self, path: &Path, names: &impl Resolve) -> Option<&Value> {
+        let (first, rest) = path.segments().split_first()?;
+        let mut value = &self.find(first, names)?.value;
+
+        for segment in rest {
+            value = child(value, segment, names)?;
+        }
+
+        Some(value)
+    }
+
+    pub fn get_path_mut(&mut self, path: &Path, names: &impl Resolve) -> Option<&mut Value> {
+        let (first, rest) = path.segments().split_first()?;
+        let mut value = &mut self.find_mut(first, names)?.value;
+
+        for segment in rest {
+            value = child_mut(value, segment, names)?;
+        }
+
+        Some(value)
+    }
+
+    /// Sets the value at `path`, returning the value it replaced.
+    ///
+    /// Missing symbols are created in their parent structure, and an array index one past the
+    /// end appends to the array. Parents must already exist, and array elements must have the
+    /// kind of their array.
+    pub fn set_path(
+        &mut self,
+        path: &Path,
+        value: Value,
+        names: &impl Resolve,
+    ) -> Result<Option<Value>> {
+        let not_found = || Error::PathNotFound(path.to_string());
+        let (last, parent) = path.segments().split_last().ok_or_else(not_found)?;
+
+        let structure = match parent.is_empty() {
+            true => self,
+            false => match self.get_path_mut(&Path::new(parent.to_vec()), names) {
+                Some(Value::Array(kind, values)) => {
+                    let Segment::Index(index) = last else {
+                        return Err(not_found());
+                    };
+                    check_kind(*kind, &value)?;
+
+                    return match *index {
+                        index if index < values.len() => {
+                            Ok(Some(std::mem::replace(&mut values[index], value)))
+                        }
+                        index if index == values.len() => {
+                            values.push(value);
+                            Ok(None)
+                        }
+                        _ => Err(not_found()),
+                    };
+                }
+                Some(Value::Structure(structure) | Value::StructurePointer(structure)) => structure,
+                _ => return Err(not_found()),
+            },
+        };
+
+        check_kind(value.kind(), &value)?;
+
+        if let Some(symbol) = structure.find_mut(last, names) {
+            // Keep local strings local when only the text changes
+            if !(symbol.kind == Kind::LocalString && value.kind() == Kind::String) {
+                symbol.kind = value.kind();
+            }
+            return Ok(Some(std::mem::replace(&mut symbol.value, value)));
+        }
+
+        let id = match last {
+            Segment::Id(id) => *id,
+            Segment::Name(name) => *names
+                .resolve(name)
+                .first()
+                .ok_or_else(|| Error::UnresolvedName(name.clone()))?,
+            Segment::Index(_) => return Err(not_found()),
+        };
+
+        structure.insert(Symbol {
+            kind: value.kind(),
+            id,
+            value,
+        });
+
+        Ok(None)
+    }
+
+    /// Removes the symbol or array element at `path`, returning its value if it was present.
+    pub fn remove_path(&mut self, path: &Path, names: &impl Resolve) -> Option<Value> {
+        let (last, parent) = path.segments().split_last()?;
+
+        let structure = match parent.is_empty() {
+            true => self,
+            false => match self.get_path_mut(&Path::new(parent.to_vec()), names)? {
+                Value::Array(_, values) => {
+                    return match last {
+                        Segment::Index(index) if *index < values.len() => {
+                            Some(values.remove(*index))
+                        }
+                        _ => None,
+                    };
+                }
+                Value::Structure(structure) | Value::StructurePointer(structure) => structure,
+                _ => return None,
+            },
+        };
+
+        let id = structure.find(last, names)?.id;
+        let value = structure.get(id).map(|symbol| symbol.value.clone());
+        structure.remove(id);
+
+        value
+    }
+}
+
+/// Checks that `value` can be stored as `kind`, and that the elements of arrays have their kind.
+fn check_kind(kind: Kind, value: &Value) -> Result<()> {
+    // Local strings hold the same values as strings
+    if value.kind() != kind && !(kind == Kind::LocalString && value.kind() == Kind::String) {
+        return Err(Error::ExpectedValueType(
+            format!("{:?}", kind),
+            value.clone(),
+        ));
+    }
+
+    if let Value::Array(kind, values) = value {
+        for element in values {
+            check_kind(*kind, element)?;
+        }
+    }
+
+    Ok(())
+}
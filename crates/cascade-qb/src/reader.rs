@@ -0,0 +1,34 @@
+use std::io::{self, Read};
+
+/// Wraps a reader and counts the bytes read through it, so errors can report where they happened.
+pub struct Reader<R> {
+    inner: R,
+    offset: u64,
+}
+
+impl<R: Read> Reader<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_offset(inner, 0)
+    }
+
+    /// Starts counting at `offset`, for readers that are already partway through a file.
+    pub fn with_offset(inner: R, offset: u64) -> Self {
+        Self { inner, offset }
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.offset += len as u64;
+        Ok(len)
+    }
+}
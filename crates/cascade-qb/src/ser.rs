@@ -0,0 +1,432 @@
+use std::fmt::Display;
+
+use cascade_crc as crc;
+use serde::ser::{self, Serialize};
+
+use crate::{path, Error, Id, Kind, Resolve, Result, Structure, Symbol, Value};
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Serde(msg.to_string())
+    }
+}
+
+/// The id a field called `name` is written under: the first id `names` resolves it to, or else
+/// its checksum.
+pub(crate) fn field_id(name: &str, names: &impl Resolve) -> Id {
+    names
+        .resolve(name)
+        .first()
+        .copied()
+        .unwrap_or(Id::Checksum(crc::key(name)))
+}
+
+/// Serializes `value` into a structure.
+///
+/// Struct fields and map keys become symbols, with names mapped to ids through `names` and
+/// hashed when they do not resolve. Map keys written as ids (`#123`, `@c8:4`) are used as-is.
+/// `None` fields are left out, sequences become arrays, and unit enum variants and `u32`s, which
+/// are mostly checksums, become names.
+pub fn to_structure<T: Serialize + ?Sized>(value: &T, names: &impl Resolve) -> Result<Structure> {
+    match value.serialize(Serializer { names })? {
+        Some(Value::Structure(structure)) => Ok(*structure),
+        Some(value) => Err(Error::ExpectedValueType("Structure".to_string(), value)),
+        None => Err(Error::Serde(
+            "expected a structure, got nothing".to_string(),
+        )),
+    }
+}
+
+fn integer<T: TryInto<i32> + Display + Copy>(v: T) -> Result<Option<Value>> {
+    v.try_into()
+        .map(|v| Some(Value::I32(v)))
+        .map_err(|_| Error::Serde(format!("integer {} does not fit in 32 bits", v)))
+}
+
+fn symbol(id: Id, value: Value) -> Symbol {
+    Symbol {
+        kind: value.kind(),
+        id,
+        value,
+    }
+}
+
+/// Serializes a single value. `None` stands for a value that is left out, like [`Option::None`].
+struct Serializer<'a, N> {
+    names: &'a N,
+}
+
+impl<N> Clone for Serializer<'_, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<N> Copy for Serializer<'_, N> {}
+
+impl<'a, N: Resolve> ser::Serializer for Serializer<'a, N> {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    type SerializeSeq = ArraySerializer<'a, N>;
+    type SerializeTuple = ArraySerializer<'a, N>;
+    type SerializeTupleStruct = ArraySerializer<'a, N>;
+    type SerializeTupleVariant = VariantSerializer<ArraySerializer<'a, N>>;
+    type SerializeMap = StructureSerializer<'a, N>;
+    type SerializeStruct = StructureSerializer<'a, N>;
+    type SerializeStructVariant = VariantSerializer<StructureSerializer<'a, N>>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok> {
+        Ok(Some(Value::I32(v as i32)))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok> {
+        Ok(Some(Value::I8(v)))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok> {
+        Ok(Some(Value::I16(v)))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok> {
+        Ok(Some(Value::I32(v)))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok> {
+        integer(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok> {
+        Ok(Some(Value::U8(v)))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok> {
+        Ok(Some(Value::U16(v)))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok> {
+        Ok(Some(Value::Name(v)))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok> {
+        let v = u32::try_from(v)
+            .map_err(|_| Error::Serde(format!("integer {} does not fit in 32 bits", v)))?;
+        self.serialize_u32(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok> {
+        Ok(Some(Value::F32(v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok> {
+        Ok(Some(Value::F32(v as f32)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok> {
+        Ok(Some(Value::String(v.as_bytes().to_vec())))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok> {
+        Ok(Some(Value::String(v.to_vec())))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok> {
+        Ok(Some(Value::Structure(Box::new(Structure::new(vec![])))))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok> {
+        Ok(Some(Value::Name(crc::key(variant))))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok> {
+        let symbols = match value.serialize(self)? {
+            Some(value) => vec![symbol(field_id(variant, self.names), value)],
+            None => vec![],
+        };
+
+        Ok(Some(Value::Structure(Box::new(Structure::new(symbols)))))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(ArraySerializer {
+            serializer: self,
+            values: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Ok(VariantSerializer {
+            id: field_id(variant, self.names),
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(StructureSerializer {
+            serializer: self,
+            symbols: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Ok(VariantSerializer {
+            id: field_id(variant, self.names),
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+struct ArraySerializer<'a, N> {
+    serializer: Serializer<'a, N>,
+    values: Vec<Value>,
+}
+
+impl<N: Resolve> ArraySerializer<'_, N> {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let value = value
+            .serialize(self.serializer)?
+            .ok_or_else(|| Error::Serde("arrays cannot hold missing values".to_string()))?;
+
+        match self.values.first() {
+            Some(first) if first.kind() != value.kind() => Err(Error::Serde(format!(
+                "array elements must share a kind, got {:?} and {:?}",
+                first.kind(),
+                value.kind()
+            ))),
+            _ => {
+                self.values.push(value);
+                Ok(())
+            }
+        }
+    }
+
+    fn finish(self) -> Value {
+        let kind = self
+            .values
+            .first()
+            .map(|value| value.kind())
+            .unwrap_or(Kind::None);
+
+        Value::Array(kind, self.values)
+    }
+}
+
+impl<N: Resolve> ser::SerializeSeq for ArraySerializer<'_, N> {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(Some(self.finish()))
+    }
+}
+
+impl<N: Resolve> ser::SerializeTuple for ArraySerializer<'_, N> {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(Some(self.finish()))
+    }
+}
+
+impl<N: Resolve> ser::SerializeTupleStruct for ArraySerializer<'_, N> {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(Some(self.finish()))
+    }
+}
+
+struct StructureSerializer<'a, N> {
+    serializer: Serializer<'a, N>,
+    symbols: Vec<Symbol>,
+    key: Option<Id>,
+}
+
+impl<N: Resolve> StructureSerializer<'_, N> {
+    fn push<T: Serialize + ?Sized>(&mut self, id: Id, value: &T) -> Result<()> {
+        if let Some(value) = value.serialize(self.serializer)? {
+            self.symbols.push(symbol(id, value));
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Value {
+        Value::Structure(Box::new(Structure::new(self.symbols)))
+    }
+}
+
+impl<N: Resolve> ser::SerializeMap for StructureSerializer<'_, N> {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(match key.serialize(self.serializer)? {
+            Some(Value::String(bytes)) => {
+                let name = String::from_utf8(bytes)
+                    .map_err(|_| Error::Serde("map keys must be valid utf-8".to_string()))?;
+
+                match path::parse_id(&name) {
+                    Some(id) => id,
+                    None => field_id(&name, self.serializer.names),
+                }
+            }
+            Some(Value::I32(checksum)) => Id::Checksum(checksum as u32),
+            Some(Value::U8(checksum)) => Id::Checksum(checksum as u32),
+            Some(Value::U16(checksum)) => Id::Checksum(checksum as u32),
+            _ => return Err(Error::Serde("map keys must be names or ids".to_string())),
+        });
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let id = self
+            .key
+            .take()
+            .ok_or_else(|| Error::Serde("map value without a key".to_string()))?;
+        self.push(id, value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(Some(self.finish()))
+    }
+}
+
+impl<N: Resolve> ser::SerializeStruct for StructureSerializer<'_, N> {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.push(field_id(key, self.serializer.names), value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(Some(self.finish()))
+    }
+}
+
+/// Wraps the content of a tuple or struct variant in a structure holding a single symbol, named
+/// after the variant.
+struct VariantSerializer<S> {
+    id: Id,
+    inner: S,
+}
+
+impl<S> VariantSerializer<S> {
+    fn finish(id: Id, value: Value) -> Option<Value> {
+        Some(Value::Structure(Box::new(Structure::new(vec![symbol(
+            id, value,
+        )]))))
+    }
+}
+
+impl<N: Resolve> ser::SerializeTupleVariant for VariantSerializer<ArraySerializer<'_, N>> {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(Self::finish(self.id, self.inner.finish()))
+    }
+}
+
+impl<N: Resolve> ser::SerializeStructVariant for VariantSerializer<StructureSerializer<'_, N>> {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(Self::finish(self.id, self.inner.finish()))
+    }
+}
@@ -3,7 +3,7 @@ use std::{
     io::{Read, Write},
 };
 
-use crate::{Error, Id, Kind, Symbol};
+use crate::{Error, Id, Kind, Path, Reader, Symbol};
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -15,12 +15,24 @@ impl Structure {
     }
 
     pub fn read(reader: &mut impl Read) -> Result<Structure, Error> {
+        Self::read_from(&mut Reader::new(reader))
+    }
+
+    /// Reads a structure, reporting error offsets relative to the start of `reader`.
+    pub fn read_from<R: Read>(reader: &mut Reader<R>) -> Result<Structure, Error> {
+        Self::read_at(reader, &Path::default())
+    }
+
+    pub(crate) fn read_at<R: Read>(
+        reader: &mut Reader<R>,
+        path: &Path,
+    ) -> Result<Structure, Error> {
         let mut symbols = vec![];
 
         while {
             // do:
             // Read symbol from the reader
-            let symbol = Symbol::read(reader)?;
+            let symbol = Symbol::read_at(reader, path)?;
             let kind = symbol.kind;
 
             // while:
@@ -68,6 +80,10 @@ impl Structure {
         self.0.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     pub fn insert(&mut self, symbol: Symbol) -> Option<Symbol> {
         match self.get_mut(symbol.id) {
             Some(existing) => {
@@ -89,6 +105,10 @@ impl Structure {
     pub fn iter(&self) -> impl Iterator<Item = &Symbol> {
         self.0.iter()
     }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Symbol> {
+        self.0.iter_mut()
+    }
 }
 
 impl FromIterator<Symbol> for Structure {
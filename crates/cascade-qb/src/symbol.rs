@@ -6,7 +6,7 @@ use std::{
 use byteorder::{ReadBytesExt, WriteBytesExt};
 
 use super::Structure;
-use crate::{Error, Id, Kind, Value};
+use crate::{Error, Id, Kind, Path, Reader, Segment, Value};
 
 const CHECKSUM_LOOKUP_MASK_8: u8 = 1 << 7;
 const CHECKSUM_LOOKUP_MASK_16: u8 = 1 << 6;
@@ -37,6 +37,22 @@ impl Symbol {
     }
 
     pub fn read(reader: &mut impl Read) -> Result<Self, Error> {
+        Self::read_at(&mut Reader::new(reader), &Path::default())
+    }
+
+    pub(crate) fn read_at<R: Read>(reader: &mut Reader<R>, parent: &Path) -> Result<Self, Error> {
+        let offset = reader.offset();
+
+        let (kind, id) = Self::read_header(reader).map_err(|err| err.at(offset, parent))?;
+
+        let path = parent.join(Segment::Id(id));
+        let value = Value::read_at(reader, kind, &path).map_err(|err| err.at(offset, &path))?;
+        let symbol = Symbol { kind, id, value };
+
+        Ok(symbol)
+    }
+
+    fn read_header(reader: &mut impl Read) -> Result<(Kind, Id), Error> {
         let type_byte = reader.read_u8()?;
 
         // 8-bit / 16-bit mask in bits 6/7
@@ -54,10 +70,7 @@ impl Symbol {
 
         let id = Id::read(reader, kind, use_lookup_8, use_lookup_16)?;
 
-        let value = Value::read(reader, kind)?;
-        let symbol = Symbol { kind, id, value };
-
-        Ok(symbol)
+        Ok((kind, id))
     }
 
     pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
@@ -0,0 +1,550 @@
+//! Human-readable text representation of QB structures.
+//!
+//! Each symbol is written as `id = value`, where the id is `#<checksum>`, `@c8:<index>`,
+//! `@c16:<index>` or `_` for none. Every literal maps to exactly one [`Kind`], so text produced
+//! by [`to_string`] parses back into a structure that writes the same bytes:
+//!
+//! | kind                | literal                          |
+//! |---------------------|----------------------------------|
+//! | `Integer`           | `42`                             |
+//! | `Float`             | `1.5`, `float(0x7fc00000)`       |
+//! | `String`            | `"text\x92"`                     |
+//! | `LocalString`       | `l"text"`                        |
+//! | `Pair`              | `(1.0, 2.0)`                     |
+//! | `Vector`            | `(1.0, 2.0, 3.0)`                |
+//! | `QScript`           | `qscript(01 16 24)`              |
+//! | `CFunction`         | `cfunction(#123)`                |
+//! | `MemberFunction`    | `member_function(#123)`          |
+//! | `Structure`         | `{ id = value ... }`             |
+//! | `StructurePointer`  | `*{ id = value ... }`            |
+//! | `Array`             | `[integer; 1, 2, 3]`             |
+//! | `Name`              | `#123`                           |
+//! | `I8`/`I16`/`U8`/`U16` | `-1i8`, `-300i16`, `1u8`, `2u16` |
+//! | `ZeroInt`/`ZeroFloat` | `zero_int`, `zero_float`       |
+//!
+//! `//` starts a comment that runs to the end of the line.
+
+use crate::{Error, Id, Kind, Result, Structure, Symbol, Value};
+
+const INDENT: &str = "    ";
+
+const KIND_NAMES: [(Kind, &str); 20] = [
+    (Kind::None, "none"),
+    (Kind::Integer, "integer"),
+    (Kind::Float, "float"),
+    (Kind::String, "string"),
+    (Kind::LocalString, "local_string"),
+    (Kind::Pair, "pair"),
+    (Kind::Vector, "vector"),
+    (Kind::QScript, "qscript"),
+    (Kind::CFunction, "cfunction"),
+    (Kind::MemberFunction, "member_function"),
+    (Kind::Structure, "structure"),
+    (Kind::StructurePointer, "structure_pointer"),
+    (Kind::Array, "array"),
+    (Kind::Name, "name"),
+    (Kind::I8, "i8"),
+    (Kind::I16, "i16"),
+    (Kind::U8, "u8"),
+    (Kind::U16, "u16"),
+    (Kind::ZeroInt, "zero_int"),
+    (Kind::ZeroFloat, "zero_float"),
+];
+
+fn kind_name(kind: Kind) -> &'static str {
+    KIND_NAMES
+        .iter()
+        .find(|(k, _)| *k == kind)
+        .map(|(_, name)| *name)
+        .unwrap_or("none")
+}
+
+fn kind_from_name(name: &str) -> Option<Kind> {
+    KIND_NAMES
+        .iter()
+        .find(|(_, n)| *n == name)
+        .map(|(kind, _)| *kind)
+}
+
+/// Writes a structure in the QB text format.
+pub fn to_string(structure: &Structure) -> String {
+    let mut out = String::new();
+    write_symbols(&mut out, structure, 0);
+    out
+}
+
+/// Parses a structure from the QB text format.
+pub fn from_str(s: &str) -> Result<Structure> {
+    let mut parser = Parser::new(s);
+    let structure = parser.parse_symbols()?;
+
+    match parser.peek() {
+        None => Ok(structure),
+        Some(c) => Err(parser.error(format!("unexpected '{}'", c as char))),
+    }
+}
+
+pub(crate) fn write_id(out: &mut String, id: Id) {
+    match id {
+        Id::None => out.push('_'),
+        Id::Checksum(v) => out.push_str(&format!("#{}", v)),
+        Id::Compress8(v) => out.push_str(&format!("@c8:{}", v)),
+        Id::Compress16(v) => out.push_str(&format!("@c16:{}", v)),
+    }
+}
+
+fn write_indent(out: &mut String, depth: usize) {
+    for _ in 0..depth {
+        out.push_str(INDENT);
+    }
+}
+
+fn write_symbols(out: &mut String, structure: &Structure, depth: usize) {
+    for symbol in structure.iter() {
+        write_indent(out, depth);
+        write_id(out, symbol.id);
+        out.push_str(" = ");
+        write_value(out, symbol.kind, &symbol.value, depth);
+        out.push('\n');
+    }
+}
+
+fn write_structure(out: &mut String, structure: &Structure, depth: usize) {
+    if structure.is_empty() {
+        out.push_str("{}");
+    } else {
+        out.push_str("{\n");
+        write_symbols(out, structure, depth + 1);
+        write_indent(out, depth);
+        out.push('}');
+    }
+}
+
+fn write_float(out: &mut String, value: f32) {
+    if value.is_finite() {
+        out.push_str(&format!("{:?}", value));
+    } else {
+        out.push_str(&format!("float(0x{:08x})", value.to_bits()));
+    }
+}
+
+fn write_string(out: &mut String, bytes: &[u8]) {
+    out.push('"');
+    for byte in bytes {
+        match byte {
+            b'"' => out.push_str("\\\""),
+            b'\\' => out.push_str("\\\\"),
+            b'\n' => out.push_str("\\n"),
+            b'\t' => out.push_str("\\t"),
+            0x20..=0x7e => out.push(*byte as char),
+            _ => out.push_str(&format!("\\x{:02x}", byte)),
+        }
+    }
+    out.push('"');
+}
+
+pub(crate) fn write_value(out: &mut String, kind: Kind, value: &Value, depth: usize) {
+    match value {
+        Value::None => out.push_str("none"),
+        Value::U8(v) => out.push_str(&format!("{}u8", v)),
+        Value::U16(v) => out.push_str(&format!("{}u16", v)),
+        Value::I8(v) => out.push_str(&format!("{}i8", v)),
+        Value::I16(v) => out.push_str(&format!("{}i16", v)),
+        Value::I32(v) => out.push_str(&v.to_string()),
+        Value::F32(v) => write_float(out, *v),
+        Value::ZeroInt => out.push_str("zero_int"),
+        Value::ZeroFloat => out.push_str("zero_float"),
+        Value::String(bytes) => {
+            if kind == Kind::LocalString {
+                out.push('l');
+            }
+            write_string(out, bytes);
+        }
+        Value::Pair(a, b) => {
+            out.push('(');
+            write_float(out, *a);
+            out.push_str(", ");
+            write_float(out, *b);
+            out.push(')');
+        }
+        Value::Vector(a, b, c) => {
+            out.push('(');
+            write_float(out, *a);
+            out.push_str(", ");
+            write_float(out, *b);
+            out.push_str(", ");
+            write_float(out, *c);
+            out.push(')');
+        }
+        Value::Structure(structure) => write_structure(out, structure, depth),
+        Value::StructurePointer(structure) => {
+            out.push('*');
+            write_structure(out, structure, depth);
+        }
+        Value::Array(element_kind, values) => {
+            out.push('[');
+            out.push_str(kind_name(*element_kind));
+            out.push(';');
+
+            match element_kind {
+                // Compound elements get a line each
+                Kind::Structure | Kind::StructurePointer | Kind::Array if !values.is_empty() => {
+                    out.push('\n');
+                    for value in values {
+                        write_indent(out, depth + 1);
+                        write_value(out, *element_kind, value, depth + 1);
+                        out.push_str(",\n");
+                    }
+                    write_indent(out, depth);
+                }
+                _ => {
+                    for (i, value) in values.iter().enumerate() {
+                        out.push_str(if i == 0 { " " } else { ", " });
+                        write_value(out, *element_kind, value, depth);
+                    }
+                }
+            }
+
+            out.push(']');
+        }
+        Value::Name(v) => out.push_str(&format!("#{}", v)),
+        Value::QScript(bytes) => {
+            out.push_str("qscript(");
+            let hex = bytes
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<Vec<_>>();
+            out.push_str(&hex.join(" "));
+            out.push(')');
+        }
+        Value::CFunction(v) => out.push_str(&format!("cfunction(#{})", v)),
+        Value::MemberFunction(v) => out.push_str(&format!("member_function(#{})", v)),
+    }
+}
+
+fn is_word_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'_' | b'.' | b'+' | b'-')
+}
+
+struct Parser<'a> {
+    src: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            src: src.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> Error {
+        let consumed = &self.src[..self.pos.min(self.src.len())];
+        let line = consumed.iter().filter(|c| **c == b'\n').count() + 1;
+        let column = self.pos
+            - consumed
+                .iter()
+                .rposition(|c| *c == b'\n')
+                .map_or(0, |i| i + 1)
+            + 1;
+
+        Error::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.src.get(self.pos) {
+            if c.is_ascii_whitespace() {
+                self.pos += 1;
+            } else if self.src[self.pos..].starts_with(b"//") {
+                while self.src.get(self.pos).is_some_and(|c| *c != b'\n') {
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.src.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<()> {
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(self.error(format!("expected '{}'", c as char))),
+        }
+    }
+
+    fn word(&mut self) -> Result<&'a str> {
+        self.skip_whitespace();
+        let start = self.pos;
+
+        while self.src.get(self.pos).copied().is_some_and(is_word_byte) {
+            self.pos += 1;
+        }
+
+        match start == self.pos {
+            true => Err(self.error("expected a word or number")),
+            // Word bytes are always ascii
+            false => Ok(std::str::from_utf8(&self.src[start..self.pos]).unwrap_or_default()),
+        }
+    }
+
+    fn number<T: std::str::FromStr>(&mut self, word: &str) -> Result<T> {
+        let parsed = match word.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16)
+                .ok()
+                .and_then(|v| v.to_string().parse().ok()),
+            None => word.parse().ok(),
+        };
+
+        parsed.ok_or_else(|| self.error(format!("invalid number \"{}\"", word)))
+    }
+
+    fn checksum(&mut self) -> Result<u32> {
+        self.expect(b'#')?;
+        let word = self.word()?;
+        self.number(word)
+    }
+
+    fn id(&mut self) -> Result<Id> {
+        match self.peek() {
+            Some(b'#') => Ok(Id::Checksum(self.checksum()?)),
+            Some(b'@') => {
+                self.pos += 1;
+                let table = self.word()?;
+                self.expect(b':')?;
+                let index = self.word()?;
+
+                match table {
+                    "c8" => Ok(Id::Compress8(self.number(index)?)),
+                    "c16" => Ok(Id::Compress16(self.number(index)?)),
+                    _ => Err(self.error(format!("unknown compressed id table \"{}\"", table))),
+                }
+            }
+            Some(b'_') => match self.word()? {
+                "_" => Ok(Id::None),
+                word => Err(self.error(format!("invalid symbol id \"{}\"", word))),
+            },
+            _ => Err(self.error("expected a symbol id")),
+        }
+    }
+
+    fn parse_symbols(&mut self) -> Result<Structure> {
+        let mut symbols = vec![];
+
+        while matches!(self.peek(), Some(b'#' | b'@' | b'_')) {
+            let id = self.id()?;
+            self.expect(b'=')?;
+            let (kind, value) = self.value()?;
+
+            symbols.push(Symbol { kind, id, value });
+        }
+
+        Ok(Structure::new(symbols))
+    }
+
+    fn structure(&mut self) -> Result<Box<Structure>> {
+        self.expect(b'{')?;
+        let structure = self.parse_symbols()?;
+        self.expect(b'}')?;
+
+        Ok(Box::new(structure))
+    }
+
+    fn float(&mut self) -> Result<f32> {
+        let word = self.word()?;
+
+        if word == "float" {
+            self.expect(b'(')?;
+            let bits = self.word()?;
+            let bits = self.number(bits)?;
+            self.expect(b')')?;
+
+            Ok(f32::from_bits(bits))
+        } else {
+            self.number(word)
+        }
+    }
+
+    fn string(&mut self) -> Result<Vec<u8>> {
+        self.expect(b'"')?;
+        let mut bytes = vec![];
+
+        loop {
+            let c = *self
+                .src
+                .get(self.pos)
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.pos += 1;
+
+            match c {
+                b'"' => break,
+                b'\\' => {
+                    let escape = self.src.get(self.pos).copied();
+                    self.pos += 1;
+
+                    bytes.push(match escape {
+                        Some(b'"') => b'"',
+                        Some(b'\\') => b'\\',
+                        Some(b'n') => b'\n',
+                        Some(b't') => b'\t',
+                        Some(b'x') => {
+                            let hex = self
+                                .src
+                                .get(self.pos..self.pos + 2)
+                                .and_then(|hex| std::str::from_utf8(hex).ok())
+                                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                                .ok_or_else(|| self.error("invalid \\x escape"))?;
+                            self.pos += 2;
+                            hex
+                        }
+                        _ => return Err(self.error("invalid escape sequence")),
+                    });
+                }
+                0x80.. => {
+                    return Err(self.error("non-ascii characters must be written as \\x escapes"));
+                }
+                _ => bytes.push(c),
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    fn value(&mut self) -> Result<(Kind, Value)> {
+        Ok(match self.peek() {
+            Some(b'{') => (Kind::Structure, Value::Structure(self.structure()?)),
+            Some(b'*') => {
+                self.pos += 1;
+                (
+                    Kind::StructurePointer,
+                    Value::StructurePointer(self.structure()?),
+                )
+            }
+            Some(b'#') => (Kind::Name, Value::Name(self.checksum()?)),
+            Some(b'"') => (Kind::String, Value::String(self.string()?)),
+            Some(b'l') if self.src.get(self.pos + 1) == Some(&b'"') => {
+                self.pos += 1;
+                (Kind::LocalString, Value::String(self.string()?))
+            }
+            Some(b'(') => {
+                self.pos += 1;
+                let a = self.float()?;
+                self.expect(b',')?;
+                let b = self.float()?;
+
+                let value = match self.eat(b',') {
+                    true => (Kind::Vector, Value::Vector(a, b, self.float()?)),
+                    false => (Kind::Pair, Value::Pair(a, b)),
+                };
+                self.expect(b')')?;
+
+                value
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                let name = self.word()?;
+                let element_kind = kind_from_name(name)
+                    .ok_or_else(|| self.error(format!("unknown kind \"{}\"", name)))?;
+                self.expect(b';')?;
+
+                let mut values = vec![];
+                while !self.eat(b']') {
+                    let (kind, value) = self.value()?;
+                    if kind != element_kind {
+                        return Err(self.error(format!(
+                            "expected {} array element, found {}",
+                            kind_name(element_kind),
+                            kind_name(kind)
+                        )));
+                    }
+                    values.push(value);
+
+                    if !self.eat(b',') {
+                        self.expect(b']')?;
+                        break;
+                    }
+                }
+
+                (Kind::Array, Value::Array(element_kind, values))
+            }
+            Some(_) => {
+                let start = self.pos;
+                let word = self.word()?;
+
+                match word {
+                    "none" => (Kind::None, Value::None),
+                    "zero_int" => (Kind::ZeroInt, Value::ZeroInt),
+                    "zero_float" => (Kind::ZeroFloat, Value::ZeroFloat),
+                    "float" => {
+                        self.pos = start;
+                        (Kind::Float, Value::F32(self.float()?))
+                    }
+                    "qscript" => {
+                        self.expect(b'(')?;
+                        let mut bytes = vec![];
+                        while !self.eat(b')') {
+                            let hex = self.word()?;
+                            bytes
+                                .push(u8::from_str_radix(hex, 16).map_err(|_| {
+                                    self.error(format!("invalid byte \"{}\"", hex))
+                                })?);
+                        }
+                        (Kind::QScript, Value::QScript(bytes))
+                    }
+                    "cfunction" | "member_function" => {
+                        self.expect(b'(')?;
+                        let checksum = self.checksum()?;
+                        self.expect(b')')?;
+
+                        match word {
+                            "cfunction" => (Kind::CFunction, Value::CFunction(checksum)),
+                            _ => (Kind::MemberFunction, Value::MemberFunction(checksum)),
+                        }
+                    }
+                    _ => self.integer_or_float(word)?,
+                }
+            }
+            None => return Err(self.error("expected a value")),
+        })
+    }
+
+    fn integer_or_float(&mut self, word: &str) -> Result<(Kind, Value)> {
+        let suffixed = [
+            ("u8", Kind::U8),
+            ("u16", Kind::U16),
+            ("i8", Kind::I8),
+            ("i16", Kind::I16),
+        ]
+        .into_iter()
+        .find_map(|(suffix, kind)| word.strip_suffix(suffix).map(|digits| (digits, kind)));
+
+        Ok(match suffixed {
+            Some((digits, Kind::U8)) => (Kind::U8, Value::U8(self.number(digits)?)),
+            Some((digits, Kind::U16)) => (Kind::U16, Value::U16(self.number(digits)?)),
+            Some((digits, Kind::I8)) => (Kind::I8, Value::I8(self.number(digits)?)),
+            Some((digits, _)) => (Kind::I16, Value::I16(self.number(digits)?)),
+            None if !word.starts_with("0x") && word.contains(['.', 'e', 'E']) => {
+                (Kind::Float, Value::F32(self.number(word)?))
+            }
+            None => (Kind::Integer, Value::I32(self.number(word)?)),
+        })
+    }
+}
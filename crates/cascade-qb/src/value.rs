@@ -1,11 +1,11 @@
 use std::{
     fmt::{self, Debug},
-    io::{Read, Write},
+    io::{self, Read, Write},
 };
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
-use crate::{Error, Kind, Structure};
+use crate::{Error, Kind, Path, Reader, Segment, Structure};
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -25,6 +25,14 @@ pub enum Value {
     Structure(Box<Structure>),
     Array(Kind, Vec<Value>),
     Name(u32),
+    /// Compiled script bytes, stored with a 32-bit length prefix.
+    QScript(Vec<u8>),
+    /// Checksum of a C function name.
+    CFunction(u32),
+    /// Checksum of a member function name.
+    MemberFunction(u32),
+    /// Pointed-to structure, stored inline like a regular structure.
+    StructurePointer(Box<Structure>),
 }
 
 impl fmt::Display for Value {
@@ -35,6 +43,14 @@ impl fmt::Display for Value {
 
 impl Value {
     pub fn read(reader: &mut impl Read, kind: Kind) -> Result<Value, Error> {
+        Self::read_at(&mut Reader::new(reader), kind, &Path::default())
+    }
+
+    pub(crate) fn read_at<R: Read>(
+        reader: &mut Reader<R>,
+        kind: Kind,
+        path: &Path,
+    ) -> Result<Value, Error> {
         Ok(match kind {
             Kind::None => Value::None,
             Kind::Integer => Value::I32(reader.read_i32::<LittleEndian>()?),
@@ -68,7 +84,7 @@ impl Value {
                 reader.read_f32::<LittleEndian>()?,
                 reader.read_f32::<LittleEndian>()?,
             ),
-            Kind::Structure => Value::Structure(Box::new(Structure::read(reader)?)),
+            Kind::Structure => Value::Structure(Box::new(Structure::read_at(reader, path)?)),
             Kind::Array => {
                 // TODO this is code reuse w/ Symbol::deserialize
                 let type_byte = reader.read_u8()?;
@@ -76,8 +92,13 @@ impl Value {
                 let len = reader.read_u16::<LittleEndian>()?;
 
                 let mut elements = vec![];
-                for _ in 0..len {
-                    elements.push(Value::read(reader, kind)?)
+                for index in 0..len {
+                    let offset = reader.offset();
+                    let path = path.join(Segment::Index(index as usize));
+
+                    elements.push(
+                        Value::read_at(reader, kind, &path).map_err(|err| err.at(offset, &path))?,
+                    )
                 }
 
                 Value::Array(kind, elements)
@@ -89,11 +110,22 @@ impl Value {
             Kind::U16 => Value::U16(reader.read_u16::<LittleEndian>()?),
             Kind::ZeroInt => Value::ZeroInt,
             Kind::ZeroFloat => Value::ZeroFloat,
-            _ => {
-                return Err(Error::NotImplemented(format!(
-                    "deserializing symbol type {:?}",
-                    kind
-                )))
+            Kind::QScript => {
+                let len = reader.read_u32::<LittleEndian>()?;
+
+                // The length comes from the file, so only allocate what is actually there
+                let mut bytes = vec![];
+                reader.by_ref().take(len as u64).read_to_end(&mut bytes)?;
+                if bytes.len() != len as usize {
+                    return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+                }
+
+                Value::QScript(bytes)
+            }
+            Kind::CFunction => Value::CFunction(reader.read_u32::<LittleEndian>()?),
+            Kind::MemberFunction => Value::MemberFunction(reader.read_u32::<LittleEndian>()?),
+            Kind::StructurePointer => {
+                Value::StructurePointer(Box::new(Structure::read_at(reader, path)?))
             }
         })
     }
@@ -128,12 +160,46 @@ impl Value {
                 }
             }
             Value::Name(val) => writer.write_u32::<LittleEndian>(*val)?,
+            Value::QScript(bytes) => {
+                writer.write_u32::<LittleEndian>(bytes.len() as u32)?;
+                writer.write_all(bytes)?;
+            }
+            Value::CFunction(val) | Value::MemberFunction(val) => {
+                writer.write_u32::<LittleEndian>(*val)?
+            }
+            Value::StructurePointer(structure) => structure.write(writer)?,
             // no value is written for these types
             Value::None | Value::ZeroFloat | Value::ZeroInt => (),
         }
         Ok(())
     }
 
+    /// The kind a symbol holding this value is written with. Strings are always
+    /// [`Kind::String`], never [`Kind::LocalString`].
+    pub fn kind(&self) -> Kind {
+        match self {
+            Value::None => Kind::None,
+            Value::U8(_) => Kind::U8,
+            Value::U16(_) => Kind::U16,
+            Value::I8(_) => Kind::I8,
+            Value::I16(_) => Kind::I16,
+            Value::I32(_) => Kind::Integer,
+            Value::F32(_) => Kind::Float,
+            Value::ZeroInt => Kind::ZeroInt,
+            Value::ZeroFloat => Kind::ZeroFloat,
+            Value::String(_) => Kind::String,
+            Value::Pair(_, _) => Kind::Pair,
+            Value::Vector(_, _, _) => Kind::Vector,
+            Value::Structure(_) => Kind::Structure,
+            Value::Array(_, _) => Kind::Array,
+            Value::Name(_) => Kind::Name,
+            Value::QScript(_) => Kind::QScript,
+            Value::CFunction(_) => Kind::CFunction,
+            Value::MemberFunction(_) => Kind::MemberFunction,
+            Value::StructurePointer(_) => Kind::StructurePointer,
+        }
+    }
+
     pub fn try_as_structure(self) -> Result<Box<Structure>, Error> {
         match self {
             Value::Structure(value) => Ok(value),
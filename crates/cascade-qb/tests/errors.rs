@@ -0,0 +1,152 @@
+use std::io;
+
+use cascade_qb as qb;
+
+fn symbol(kind: qb::Kind, id: u32, value: qb::Value) -> qb::Symbol {
+    qb::Symbol {
+        kind,
+        id: qb::Id::Checksum(id),
+        value,
+    }
+}
+
+// {#1 = {#2 = 5, #3 = [integer; 1, 2]}}
+fn nested_bytes() -> Vec<u8> {
+    let inner = qb::Structure::new(vec![
+        symbol(qb::Kind::Integer, 2, qb::Value::I32(5)),
+        symbol(
+            qb::Kind::Array,
+            3,
+            qb::Value::Array(
+                qb::Kind::Integer,
+                vec![qb::Value::I32(1), qb::Value::I32(2)],
+            ),
+        ),
+    ]);
+
+    qb::Structure::new(vec![symbol(
+        qb::Kind::Structure,
+        1,
+        qb::Value::Structure(Box::new(inner)),
+    )])
+    .raw_bytes()
+    .expect("could not write structure")
+}
+
+#[test]
+fn invalid_type_reports_offset_and_path() {
+    let mut bytes = nested_bytes();
+    // Type byte of #3
+    bytes[14] = 0x3f;
+
+    let err = qb::Structure::read(&mut bytes.as_slice()).expect_err("read should fail");
+
+    match err {
+        qb::Error::At {
+            offset,
+            path,
+            source,
+        } => {
+            assert_eq!(offset, 14);
+            assert_eq!(path.to_string(), "#1");
+            assert!(matches!(*source, qb::Error::InvalidType(0x3f)));
+        }
+        err => panic!("expected error with context, got {}", err),
+    }
+}
+
+#[test]
+fn truncated_array_reports_element_path() {
+    let bytes = nested_bytes();
+    // Cut off in the middle of #3[1]
+    let bytes = &bytes[..28];
+
+    let err = qb::Structure::read(&mut &bytes[..]).expect_err("read should fail");
+
+    match err {
+        qb::Error::At {
+            offset,
+            path,
+            source,
+        } => {
+            assert_eq!(offset, 26);
+            assert_eq!(path.to_string(), "#1.#3[1]");
+            assert!(matches!(
+                *source,
+                qb::Error::Io(io::ErrorKind::UnexpectedEof)
+            ));
+        }
+        err => panic!("expected error with context, got {}", err),
+    }
+}
+
+#[test]
+fn offsets_continue_from_reader() {
+    let mut bytes = vec![0xff; 20];
+    bytes.extend(nested_bytes());
+    bytes[20 + 14] = 0x3f;
+
+    let mut reader = qb::Reader::new(bytes.as_slice());
+    let mut header = [0; 20];
+    io::Read::read_exact(&mut reader, &mut header).expect("could not read header");
+
+    match qb::Structure::read_from(&mut reader) {
+        Err(qb::Error::At { offset, .. }) => assert_eq!(offset, 34),
+        result => panic!("expected error with context, got {:?}", result),
+    }
+}
+
+#[test]
+fn oversized_qscript_length_fails() {
+    let mut bytes = qb::Structure::new(vec![symbol(
+        qb::Kind::QScript,
+        1,
+        qb::Value::QScript(vec![1, 2, 3]),
+    )])
+    .raw_bytes()
+    .expect("could not write structure");
+
+    let len = bytes
+        .windows(7)
+        .position(|window| window == [3, 0, 0, 0, 1, 2, 3])
+        .expect("could not find qscript length");
+    bytes[len..len + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+
+    match qb::Structure::read(&mut bytes.as_slice()) {
+        Err(qb::Error::At { source, .. }) => assert!(matches!(
+            *source,
+            qb::Error::Io(io::ErrorKind::UnexpectedEof)
+        )),
+        result => panic!("expected error with context, got {:?}", result),
+    }
+}
+
+#[test]
+fn set_path_rejects_mismatched_array_elements() {
+    let mut structure = qb::Structure::read(&mut nested_bytes().as_slice()).unwrap();
+    let array: qb::Path = "#1.#3".parse().unwrap();
+
+    for index in [0, 2] {
+        let element = array.join(qb::Segment::Index(index));
+        assert!(matches!(
+            structure.set_path(&element, qb::Value::F32(1.0), &()),
+            Err(qb::Error::ExpectedValueType(..))
+        ));
+    }
+    assert!(matches!(
+        structure.set_path(
+            &"#1.#4".parse().unwrap(),
+            qb::Value::Array(qb::Kind::Integer, vec![qb::Value::Name(1)]),
+            &()
+        ),
+        Err(qb::Error::ExpectedValueType(..))
+    ));
+
+    structure
+        .set_path(&array.join(qb::Segment::Index(2)), qb::Value::I32(3), &())
+        .expect("could not append element");
+    assert!(matches!(
+        structure.get_path(&array, &()),
+        Some(qb::Value::Array(qb::Kind::Integer, values)) if values.len() == 3
+    ));
+}
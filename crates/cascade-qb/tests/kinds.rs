@@ -0,0 +1,120 @@
+use cascade_qb as qb;
+
+fn symbol(kind: qb::Kind, id: u32, value: qb::Value) -> qb::Symbol {
+    qb::Symbol {
+        kind,
+        id: qb::Id::Checksum(id),
+        value,
+    }
+}
+
+fn all_kinds() -> qb::Structure {
+    let nested = qb::Structure::new(vec![symbol(qb::Kind::Integer, 1, qb::Value::I32(-7))]);
+
+    qb::Structure::new(vec![
+        symbol(qb::Kind::Integer, 1, qb::Value::I32(42)),
+        symbol(qb::Kind::Float, 2, qb::Value::F32(1.5)),
+        symbol(qb::Kind::String, 3, qb::Value::String(b"string".to_vec())),
+        symbol(
+            qb::Kind::LocalString,
+            4,
+            qb::Value::String(b"local".to_vec()),
+        ),
+        symbol(qb::Kind::Pair, 5, qb::Value::Pair(f32::NAN, -0.)),
+        symbol(qb::Kind::Vector, 6, qb::Value::Vector(1., 2., 3.)),
+        symbol(
+            qb::Kind::QScript,
+            7,
+            qb::Value::QScript(vec![0x01, 0x16, 0x24]),
+        ),
+        symbol(qb::Kind::CFunction, 8, qb::Value::CFunction(0xdeadbeef)),
+        symbol(
+            qb::Kind::MemberFunction,
+            9,
+            qb::Value::MemberFunction(0xcafebabe),
+        ),
+        symbol(
+            qb::Kind::Structure,
+            10,
+            qb::Value::Structure(Box::new(nested.clone())),
+        ),
+        symbol(
+            qb::Kind::StructurePointer,
+            11,
+            qb::Value::StructurePointer(Box::new(nested)),
+        ),
+        symbol(
+            qb::Kind::Array,
+            12,
+            qb::Value::Array(
+                qb::Kind::CFunction,
+                vec![qb::Value::CFunction(1), qb::Value::CFunction(2)],
+            ),
+        ),
+        symbol(qb::Kind::Name, 13, qb::Value::Name(0x12345678)),
+        symbol(qb::Kind::I8, 14, qb::Value::I8(-1)),
+        symbol(qb::Kind::I16, 15, qb::Value::I16(-300)),
+        symbol(qb::Kind::U8, 16, qb::Value::U8(255)),
+        symbol(qb::Kind::U16, 17, qb::Value::U16(65535)),
+        symbol(qb::Kind::ZeroInt, 18, qb::Value::ZeroInt),
+        symbol(qb::Kind::ZeroFloat, 19, qb::Value::ZeroFloat),
+    ])
+}
+
+#[test]
+fn round_trip_all_kinds() {
+    let bytes = all_kinds().raw_bytes().expect("could not write structure");
+
+    let structure =
+        qb::Structure::read(&mut bytes.as_slice()).expect("could not read structure back");
+
+    assert_eq!(structure.len(), 19);
+    assert_eq!(
+        structure.raw_bytes().expect("could not rewrite structure"),
+        bytes
+    );
+}
+
+#[test]
+fn text_round_trip_all_kinds() {
+    let structure = all_kinds();
+    let text = qb::text::to_string(&structure);
+
+    let parsed = qb::text::from_str(&text).expect("could not parse text");
+
+    assert_eq!(
+        parsed
+            .raw_bytes()
+            .expect("could not write parsed structure"),
+        structure.raw_bytes().expect("could not write structure")
+    );
+}
+
+#[test]
+fn text_round_trip_id_none() {
+    let nested = qb::Structure::new(vec![qb::Symbol {
+        kind: qb::Kind::Integer,
+        id: qb::Id::None,
+        value: qb::Value::I32(1),
+    }]);
+    let structure = qb::Structure::new(vec![qb::Symbol {
+        kind: qb::Kind::Structure,
+        id: qb::Id::None,
+        value: qb::Value::Structure(Box::new(nested)),
+    }]);
+
+    let text = qb::text::to_string(&structure);
+    let parsed = qb::text::from_str(&text).expect("could not parse text");
+
+    assert!(matches!(
+        parsed.iter().next().map(|symbol| symbol.id),
+        Some(qb::Id::None)
+    ));
+    assert_eq!(qb::text::to_string(&parsed), text);
+    assert_eq!(
+        parsed
+            .raw_bytes()
+            .expect("could not write parsed structure"),
+        structure.raw_bytes().expect("could not write structure")
+    );
+}
@@ -0,0 +1,138 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use cascade_qb as qb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum BodyShape {
+    Standard,
+    Custom { scale: f32 },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Skater {
+    name: String,
+    is_male: bool,
+    total_score: i32,
+    hat: Option<u8>,
+    glasses: Option<u8>,
+    trick_mapping: Vec<u16>,
+    position: (f32, f32, f32),
+    body_shape: BodyShape,
+    extra: BTreeMap<String, i32>,
+}
+
+/// Resolves `name` to compress8 id 43, like the thugpro compress table.
+struct Names;
+
+impl qb::Resolve for Names {
+    fn resolve(&self, name: &str) -> Vec<qb::Id> {
+        match name {
+            "name" => vec![qb::Id::Compress8(43)],
+            _ => vec![],
+        }
+    }
+}
+
+fn skater() -> Skater {
+    Skater {
+        name: "Tony".to_string(),
+        is_male: true,
+        total_score: 1_000_000,
+        hat: Some(3),
+        glasses: None,
+        trick_mapping: vec![1, 2, 3],
+        position: (1., 2., 3.),
+        body_shape: BodyShape::Custom { scale: 0.5 },
+        extra: BTreeMap::from([("#1234".to_string(), 5), ("@c8:7".to_string(), 6)]),
+    }
+}
+
+#[test]
+fn round_trip_struct() {
+    let structure = qb::to_structure(&skater(), &Names).expect("could not serialize");
+    let parsed: Skater = qb::from_structure(&structure, &Names).expect("could not deserialize");
+
+    assert_eq!(parsed, skater());
+}
+
+#[test]
+fn fields_map_to_ids() {
+    let structure = qb::to_structure(&skater(), &Names).expect("could not serialize");
+
+    let name = structure
+        .get(qb::Id::Compress8(43))
+        .expect("name should use the resolved id");
+    assert!(matches!(&name.value, qb::Value::String(name) if name == b"Tony"));
+
+    let total_score = structure
+        .get(qb::Id::Checksum(236215964))
+        .expect("total_score should be hashed");
+    assert!(matches!(total_score.value, qb::Value::I32(1_000_000)));
+
+    // extra, hashed, holding ids used as-is
+    assert!(structure.get(qb::Id::Checksum(1234)).is_none());
+    assert!(matches!(
+        structure.get_path(&"#2998989466.#1234".parse().unwrap(), &Names),
+        Some(qb::Value::I32(5))
+    ));
+    assert!(matches!(
+        structure.get_path(&"#2998989466.@c8:7".parse().unwrap(), &Names),
+        Some(qb::Value::I32(6))
+    ));
+
+    // None fields are left out
+    assert_eq!(structure.len(), 8);
+}
+
+#[test]
+fn unit_variants_are_names() {
+    let mut skater = skater();
+    skater.body_shape = BodyShape::Standard;
+
+    let structure = qb::to_structure(&skater, &()).expect("could not serialize");
+
+    // body_shape, hashed
+    assert!(matches!(
+        structure.get(qb::Id::Checksum(2166785263)),
+        Some(qb::Symbol {
+            value: qb::Value::Name(_),
+            ..
+        })
+    ));
+
+    let parsed: Skater = qb::from_structure(&structure, &()).expect("could not deserialize");
+    assert_eq!(parsed.body_shape, BodyShape::Standard);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Checksums {
+    small: u32,
+    large: u32,
+    wide: u64,
+}
+
+#[test]
+fn u32s_are_names() {
+    let checksums = Checksums {
+        small: 5,
+        large: 0xdeadbeef,
+        wide: u32::MAX as u64,
+    };
+
+    let structure = qb::to_structure(&checksums, &()).expect("could not serialize");
+    assert!(structure
+        .iter()
+        .all(|symbol| matches!(symbol.value, qb::Value::Name(_))));
+
+    let parsed: Checksums = qb::from_structure(&structure, &()).expect("could not deserialize");
+    assert_eq!(parsed, checksums);
+
+    let too_wide = Checksums {
+        wide: 1 << 32,
+        ..checksums
+    };
+    assert!(qb::to_structure(&too_wide, &()).is_err());
+}
@@ -9,6 +9,25 @@ pub enum Error {
 
     #[error("qb error: {0}")]
     Qb(#[from] qb::Error),
+
+    #[error(
+        "{field} mismatch: header has {expected:#010x}, but the contents hash to {actual:#010x}"
+    )]
+    ChecksumMismatch {
+        field: &'static str,
+        expected: u32,
+        actual: u32,
+    },
+
+    #[error("save is {len} bytes, but must fit in {max} bytes")]
+    SaveTooLarge { len: usize, max: usize },
+
+    #[error("{field} mismatch: header has {expected} bytes, but {actual} bytes were read")]
+    SizeMismatch {
+        field: &'static str,
+        expected: i32,
+        actual: i32,
+    },
 }
 
 impl From<io::Error> for Error {
@@ -1,5 +1,7 @@
 mod error;
+mod repair;
 mod save;
 
 pub use error::{Error, Result};
-pub use save::{Header, Save};
+pub use repair::{Fix, repair};
+pub use save::{Header, ReadOptions, SAVE_FILE_SIZE, Save};
@@ -0,0 +1,112 @@
+use std::fmt;
+
+use crate::{
+    Result, Save,
+    save::{PADDING_BYTE, ReadOptions, SAVE_FILE_SIZE},
+};
+
+/// Something [`repair`] changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fix {
+    Checksum {
+        field: &'static str,
+        old: u32,
+        new: u32,
+    },
+    Size {
+        field: &'static str,
+        old: i32,
+        new: i32,
+    },
+    /// The padding after the data was the wrong length.
+    Padding { old: usize, new: usize },
+    /// Bytes other than padding after the data, which were dropped.
+    TrailingGarbage { offset: usize, len: usize },
+}
+
+impl fmt::Display for Fix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fix::Checksum { field, old, new } => {
+                write!(f, "{}: {:#010x} -> {:#010x}", field, old, new)
+            }
+            Fix::Size { field, old, new } => write!(f, "{}: {} -> {}", field, old, new),
+            Fix::Padding { old, new } => {
+                write!(f, "padding: {} -> {} bytes", old, new)
+            }
+            Fix::TrailingGarbage { offset, len } => {
+                write!(
+                    f,
+                    "dropped {} bytes of trailing garbage at {:#x}",
+                    len, offset
+                )
+            }
+        }
+    }
+}
+
+/// Loads a save whose header or padding is damaged, fixing its header to match its contents.
+///
+/// The symbols themselves still have to parse. The trailer is normalized, so writing the
+/// returned save pads it back to full size, without any trailing garbage.
+pub fn repair(bytes: &[u8]) -> Result<(Save, Vec<Fix>)> {
+    let mut save = Save::read_with(&mut &bytes[..], ReadOptions::lenient())?;
+    let header = save.calculate_header()?;
+
+    let mut fixes = vec![];
+
+    let sizes = [
+        (
+            "summary_size",
+            save.header.summary_size,
+            header.summary_size,
+        ),
+        ("total_size", save.header.total_size, header.total_size),
+    ];
+    for (field, old, new) in sizes {
+        if old != new {
+            fixes.push(Fix::Size { field, old, new });
+        }
+    }
+
+    let checksums = [
+        (
+            "summary_checksum",
+            save.header.summary_checksum,
+            header.summary_checksum,
+        ),
+        ("checksum", save.header.checksum, header.checksum),
+    ];
+    for (field, old, new) in checksums {
+        if old != new {
+            fixes.push(Fix::Checksum { field, old, new });
+        }
+    }
+
+    let end = (header.total_size as usize).min(bytes.len());
+    let trailer = &bytes[end..];
+    let padding = trailer
+        .iter()
+        .take_while(|byte| **byte == PADDING_BYTE)
+        .count();
+
+    let expected_padding = SAVE_FILE_SIZE.saturating_sub(end);
+    if padding != expected_padding {
+        fixes.push(Fix::Padding {
+            old: padding,
+            new: expected_padding,
+        });
+    }
+
+    if padding < trailer.len() {
+        fixes.push(Fix::TrailingGarbage {
+            offset: end + padding,
+            len: trailer.len() - padding,
+        });
+    }
+
+    save.header = header;
+    save.normalize_trailer();
+
+    Ok((save, fixes))
+}
@@ -1,14 +1,15 @@
-use std::io::{Read, Write};
+use std::io::{self, Read, Write};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use cascade_crc as crc;
 use cascade_qb as qb;
 use count_write::CountWrite;
 
-use crate::Result;
+use crate::{Error, Result};
 
-const SAVE_FILE_SIZE: usize = 90112;
-const PADDING_BYTE: u8 = 0x69;
+/// The size every save is padded to. The game can't load anything larger.
+pub const SAVE_FILE_SIZE: usize = 90112;
+pub(crate) const PADDING_BYTE: u8 = 0x69;
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -52,6 +53,121 @@ impl Header {
 
         Ok(bytes)
     }
+
+    /// Calculates the header for an encoded summary and data.
+    fn calculate(summary_bytes: &[u8], data_bytes: &[u8], version: i32) -> Result<Self> {
+        let summary_checksum = crc::checksum(summary_bytes);
+        let summary_size = summary_bytes.len() as i32;
+        let data_size = data_bytes.len() as i32;
+        let total_size = size_of::<Header>() as i32 + summary_size + data_size;
+
+        let header_zero_checksum = Header {
+            checksum: 0,
+            summary_checksum,
+            summary_size,
+            total_size,
+            version,
+        };
+
+        let mut hasher = crc::Hasher::new();
+        header_zero_checksum.write(&mut hasher)?;
+        hasher.update(summary_bytes);
+        hasher.update(data_bytes);
+
+        let checksum = hasher.finalize();
+
+        Ok(Header {
+            checksum,
+            summary_checksum,
+            summary_size,
+            total_size,
+            version,
+        })
+    }
+
+    /// Checks the sizes and checksums against the summary and data as they were read.
+    fn verify(&self, summary_bytes: &[u8], data_bytes: &[u8]) -> Result<()> {
+        let actual = Self::calculate(summary_bytes, data_bytes, self.version)?;
+
+        let sizes = [
+            ("summary_size", self.summary_size, actual.summary_size),
+            ("total_size", self.total_size, actual.total_size),
+        ];
+        for (field, expected, actual) in sizes {
+            if expected != actual {
+                return Err(Error::SizeMismatch {
+                    field,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        let checksums = [
+            (
+                "summary_checksum",
+                self.summary_checksum,
+                actual.summary_checksum,
+            ),
+            ("checksum", self.checksum, actual.checksum),
+        ];
+        for (field, expected, actual) in checksums {
+            if expected != actual {
+                return Err(Error::ChecksumMismatch {
+                    field,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn headroom(len: usize) -> Result<usize> {
+    SAVE_FILE_SIZE.checked_sub(len).ok_or(Error::SaveTooLarge {
+        len,
+        max: SAVE_FILE_SIZE,
+    })
+}
+
+/// How [`Save::read_with`] treats a save whose header doesn't match its contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadOptions {
+    /// Fail with [`Error::SizeMismatch`] or [`Error::ChecksumMismatch`] instead of accepting
+    /// the save as it is.
+    pub strict: bool,
+}
+
+impl ReadOptions {
+    pub fn strict() -> Self {
+        Self { strict: true }
+    }
+
+    pub fn lenient() -> Self {
+        Self { strict: false }
+    }
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        Self::strict()
+    }
+}
+
+/// Keeps a copy of everything read, so it can be checked against the header afterwards.
+struct Record<R> {
+    inner: R,
+    bytes: Vec<u8>,
+}
+
+impl<R: Read> Read for Record<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let num_bytes_read = self.inner.read(buf)?;
+        self.bytes.extend_from_slice(&buf[..num_bytes_read]);
+        Ok(num_bytes_read)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -60,74 +176,112 @@ pub struct Save {
 
     pub summary: Box<qb::Structure>,
     pub data: Box<qb::Structure>,
+
+    /// The bytes after the data, if they weren't the usual padding. Written back in place of
+    /// the padding as long as they still fit.
+    pub trailer: Option<Vec<u8>>,
 }
 
 impl Save {
+    /// Reads a save, failing if its header doesn't match its contents.
     pub fn read(reader: &mut impl Read) -> Result<Self> {
-        Ok(Self {
-            header: Header::read(reader)?,
-            summary: Box::new(qb::Structure::read(reader)?),
-            data: Box::new(qb::Structure::read(reader)?),
-        })
+        Self::read_with(reader, ReadOptions::default())
     }
 
-    pub fn write<W: Write>(&self, writer: &mut W) -> Result<()> {
-        let mut count_writer = CountWrite::from(writer);
+    pub fn read_with(reader: &mut impl Read, options: ReadOptions) -> Result<Self> {
+        // Track the position so qb errors report offsets from the start of the file
+        let mut reader = qb::Reader::new(Record {
+            inner: reader,
+            bytes: vec![],
+        });
 
-        // TODO fix this
-        let header = self.calculate_header()?;
-        header.write(&mut count_writer)?;
+        let header = Header::read(&mut reader)?;
+        let summary = qb::Structure::read_from(&mut reader)?;
+        let summary_end = reader.offset() as usize;
+        let data = qb::Structure::read_from(&mut reader)?;
+        let data_end = reader.offset() as usize;
 
-        self.summary.write(&mut count_writer)?;
-        self.data.write(&mut count_writer)?;
+        let mut trailer = vec![];
+        reader.read_to_end(&mut trailer)?;
 
-        let num_bytes_written = count_writer.count() as usize;
+        if options.strict {
+            let bytes = reader.into_inner().bytes;
+            header.verify(
+                &bytes[size_of::<Header>()..summary_end],
+                &bytes[summary_end..data_end],
+            )?;
+        }
 
-        let num_padding_bytes = SAVE_FILE_SIZE.saturating_sub(num_bytes_written);
+        let is_padding = trailer.len() == SAVE_FILE_SIZE.saturating_sub(data_end)
+            && trailer.iter().all(|byte| *byte == PADDING_BYTE);
 
-        // log::info!(
-        //     "wrote {} bytes, padding with {} bytes to fill {} bytes",
-        //     num_bytes_written,
-        //     num_padding_bytes,
-        //     SAVE_FILE_SIZE
-        // );
+        Ok(Self {
+            header,
+            summary: Box::new(summary),
+            data: Box::new(data),
+            trailer: (!is_padding).then_some(trailer),
+        })
+    }
+
+    /// Replaces the trailer with the usual padding when the save is next written.
+    pub fn normalize_trailer(&mut self) {
+        self.trailer = None;
+    }
 
-        count_writer.write(&vec![PADDING_BYTE; num_padding_bytes])?;
+    /// Writes the save, followed by padding up to [`SAVE_FILE_SIZE`].
+    ///
+    /// A trailer the save was read with takes the place of the padding: as it is if the save is
+    /// unchanged, or else cut or padded so the file still ends at [`SAVE_FILE_SIZE`].
+    ///
+    /// Fails with [`Error::SaveTooLarge`] before writing anything if the save doesn't fit.
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let summary_bytes = self.summary.raw_bytes()?;
+        let data_bytes = self.data.raw_bytes()?;
+
+        let header = Header::calculate(&summary_bytes, &data_bytes, self.header.version)?;
+        let num_padding_bytes = headroom(header.total_size as usize)?;
+
+        header.write(writer)?;
+        writer.write_all(&summary_bytes)?;
+        writer.write_all(&data_bytes)?;
+        let unchanged = header.raw_bytes()? == self.header.raw_bytes()?;
+        match &self.trailer {
+            Some(trailer) if unchanged && trailer.len() <= num_padding_bytes => {
+                writer.write_all(trailer)?
+            }
+            Some(trailer) => {
+                let trailer = &trailer[..trailer.len().min(num_padding_bytes)];
+                writer.write_all(trailer)?;
+                writer.write_all(&vec![PADDING_BYTE; num_padding_bytes - trailer.len()])?;
+            }
+            None => writer.write_all(&vec![PADDING_BYTE; num_padding_bytes])?,
+        }
 
         Ok(())
     }
 
-    fn calculate_header(&self) -> Result<Header> {
-        let mut summary_bytes = self.summary.raw_bytes()?;
-        let mut data_bytes = self.data.raw_bytes()?;
+    /// The number of bytes the save takes up before padding.
+    pub fn encoded_len(&self) -> Result<usize> {
+        let mut count_writer = CountWrite::from(io::sink());
 
-        let summary_checksum = crc::checksum(&summary_bytes);
-        let summary_size = summary_bytes.len() as i32;
-        let data_size = data_bytes.len() as i32;
-        let total_size = size_of::<Header>() as i32 + summary_size + data_size;
-        let version = self.header.version;
-
-        let header_zero_checksum = Header {
-            checksum: 0,
-            summary_checksum,
-            summary_size,
-            total_size,
-            version,
-        };
+        self.summary.write(&mut count_writer)?;
+        self.data.write(&mut count_writer)?;
 
-        let mut all_bytes = vec![];
-        all_bytes.append(&mut header_zero_checksum.raw_bytes()?);
-        all_bytes.append(&mut summary_bytes);
-        all_bytes.append(&mut data_bytes);
+        Ok(size_of::<Header>() + count_writer.count() as usize)
+    }
 
-        let checksum = crc::checksum(&all_bytes);
+    /// The number of bytes the save can grow by before it no longer fits in
+    /// [`SAVE_FILE_SIZE`], or [`Error::SaveTooLarge`] if it already doesn't.
+    pub fn headroom(&self) -> Result<usize> {
+        headroom(self.encoded_len()?)
+    }
 
-        Ok(Header {
-            checksum,
-            summary_checksum,
-            summary_size,
-            total_size,
-            version,
-        })
+    /// The header matching the current summary and data.
+    pub fn calculate_header(&self) -> Result<Header> {
+        Header::calculate(
+            &self.summary.raw_bytes()?,
+            &self.data.raw_bytes()?,
+            self.header.version,
+        )
     }
 }
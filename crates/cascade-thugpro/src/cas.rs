@@ -3,28 +3,6 @@ use cascade_save as save;
 
 use crate::{Error, Result, id};
 
-fn expect_symbol(parent: &Box<qb::Structure>, id: qb::Id) -> Result<qb::Symbol> {
-    Ok(parent.get(id).ok_or(Error::SymbolNotFound(id))?.clone())
-}
-
-fn expect_symbol_mut(parent: &mut Box<qb::Structure>, id: qb::Id) -> Result<&mut qb::Symbol> {
-    Ok(parent.get_mut(id).ok_or(Error::SymbolNotFound(id))?)
-}
-
-// expect symbol and expect structure
-fn expect_structure(parent: Box<qb::Structure>, id: qb::Id) -> Result<Box<qb::Structure>> {
-    let symbol = expect_symbol(&parent, id)?;
-    Ok(symbol.value.try_as_structure()?)
-}
-
-fn expect_structure_mut(
-    parent: &mut Box<qb::Structure>,
-    id: qb::Id,
-) -> Result<&mut Box<qb::Structure>> {
-    let symbol = expect_symbol_mut(parent, id)?;
-    Ok(symbol.value.try_as_structure_mut()?)
-}
-
 #[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Item {
@@ -66,6 +44,38 @@ impl From<Option<qb::Symbol>> for Item {
     }
 }
 
+/// An [`Item`] and the path of its symbol in the save data.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PathItem {
+    /// The ids leading to the symbol, starting from the save data.
+    pub path: qb::Path,
+    pub item: Item,
+}
+
+impl PathItem {
+    pub fn modify(&self, data: &mut qb::Structure) -> Result<()> {
+        let not_found = || qb::Error::PathNotFound(self.path.to_string());
+
+        let Some(qb::Segment::Id(id)) = self.path.last() else {
+            return Err(not_found().into());
+        };
+
+        let structure = match self.path.parent() {
+            Some(parent) if !parent.is_empty() => match data.get_path_mut(&parent, &()) {
+                Some(qb::Value::Structure(structure) | qb::Value::StructurePointer(structure)) => {
+                    structure.as_mut()
+                }
+                _ => return Err(not_found().into()),
+            },
+            _ => data,
+        };
+
+        self.item.modify(structure, *id);
+        Ok(())
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Default)]
 pub struct Cas {
@@ -90,6 +100,15 @@ impl Cas {
         self.data.modify(&mut save.data)?;
         Ok(())
     }
+
+    /// Whether `save` already matches this cas, i.e. modifying it would change nothing.
+    pub fn is_applied(&self, save: &save::Save) -> Result<bool> {
+        let mut modified = save.clone();
+        self.modify(&mut modified)?;
+
+        Ok(modified.summary.raw_bytes()? == save.summary.raw_bytes()?
+            && modified.data.raw_bytes()? == save.data.raw_bytes()?)
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -135,381 +154,193 @@ impl TryFrom<Box<qb::Structure>> for Summary {
     }
 }
 
-#[derive(Debug, Clone, Default)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Data {
-    pub custom_skater: CustomSkater,
-    pub story_skater: StorySkater,
+fn path(ids: &[qb::Id]) -> qb::Path {
+    qb::Path::new(ids.iter().copied().map(qb::Segment::Id).collect())
 }
 
-impl TryFrom<Box<qb::Structure>> for Data {
-    type Error = Error;
-
-    fn try_from(data: Box<qb::Structure>) -> Result<Self> {
-        Ok(Self {
-            custom_skater: CustomSkater::try_from(expect_structure(
-                Box::clone(&data),
-                id::CUSTOM_SKATER,
-            )?)?,
-            story_skater: StorySkater::try_from(expect_structure(
-                Box::clone(&data),
-                id::STORY_SKATER,
-            )?)?,
-        })
-    }
+pub fn appearance_path() -> qb::Path {
+    path(&[id::CUSTOM_SKATER, id::CUSTOM, id::APPEARANCE])
 }
 
-impl Data {
-    pub fn modify(&self, data: &mut Box<qb::Structure>) -> Result<()> {
-        self.custom_skater
-            .modify(expect_structure_mut(data, id::CUSTOM_SKATER)?)?;
-
-        self.story_skater
-            .modify(expect_structure_mut(data, id::STORY_SKATER)?);
-
-        Ok(())
-    }
+pub fn info_path() -> qb::Path {
+    path(&[id::CUSTOM_SKATER, id::CUSTOM, id::INFO])
 }
 
-#[derive(Debug, Clone, Default)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct CustomSkater {
-    pub custom: Custom,
+pub fn story_skater_path() -> qb::Path {
+    path(&[id::STORY_SKATER])
 }
 
-impl TryFrom<Box<qb::Structure>> for CustomSkater {
-    type Error = Error;
+/// The items of the save data, keyed by the path of their symbol.
+///
+/// Read from a save, it holds every symbol under `appearance`, `info` and `story_skater`, and
+/// [`Item::Vacant`] for the known parts the save does not have.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Data(Vec<PathItem>);
 
-    fn try_from(custom_skater: Box<qb::Structure>) -> Result<Self> {
-        Ok(Self {
-            custom: Custom::try_from(expect_structure(custom_skater, id::CUSTOM)?)?,
-        })
+impl Data {
+    pub fn get(&self, path: &qb::Path) -> Option<&Item> {
+        self.0
+            .iter()
+            .find(|item| &item.path == path)
+            .map(|item| &item.item)
     }
-}
 
-impl CustomSkater {
-    pub fn modify(&self, custom_skater: &mut Box<qb::Structure>) -> Result<()> {
-        self.custom
-            .modify(expect_structure_mut(custom_skater, id::CUSTOM)?)?;
-        Ok(())
+    /// Sets the item at `path`, replacing the item already there.
+    pub fn set(&mut self, path: qb::Path, item: Item) {
+        match self.0.iter_mut().find(|other| other.path == path) {
+            Some(other) => other.item = item,
+            None => self.0.push(PathItem { path, item }),
+        }
     }
-}
-
-#[derive(Debug, Clone, Default)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Custom {
-    pub appearance: Appearance,
-    pub info: Info,
-}
 
-impl TryFrom<Box<qb::Structure>> for Custom {
-    type Error = Error;
-
-    fn try_from(custom: Box<qb::Structure>) -> Result<Self> {
-        Ok(Self {
-            appearance: Appearance::try_from(expect_structure(
-                Box::clone(&custom),
-                id::APPEARANCE,
-            )?)?,
-            info: Info::try_from(expect_structure(custom, id::INFO)?)?,
-        })
+    pub fn iter(&self) -> impl Iterator<Item = &PathItem> {
+        self.0.iter()
     }
-}
 
-impl Custom {
-    pub fn modify(&self, custom: &mut Box<qb::Structure>) -> Result<()> {
-        self.appearance
-            .modify(expect_structure_mut(custom, id::APPEARANCE)?);
-        self.info.modify(expect_structure_mut(custom, id::INFO)?);
+    pub fn modify(&self, data: &mut qb::Structure) -> Result<()> {
+        for item in &self.0 {
+            item.modify(data)?;
+        }
 
         Ok(())
     }
 }
 
-#[derive(Debug, Clone, Default)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Info {
-    pub trick_mapping: Item,
-    pub specials: Item,
-}
-
-impl TryFrom<Box<qb::Structure>> for Info {
-    type Error = Error;
-
-    fn try_from(info: Box<qb::Structure>) -> Result<Self> {
-        Ok(Self {
-            trick_mapping: info.get(id::TRICK_MAPPING).cloned().into(),
-            specials: info.get(id::SPECIALS).cloned().into(),
-        })
-    }
-}
-
-impl Info {
-    pub fn modify(&self, info: &mut qb::Structure) {
-        self.trick_mapping.modify(info, id::TRICK_MAPPING);
-        self.specials.modify(info, id::SPECIALS);
-    }
-}
-
-#[derive(Debug, Clone, Default)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Appearance {
-    pub body_shape: Item,
-    pub body: Item,
-
-    pub skater_m_head: Item,
-    pub skater_f_head: Item,
-    pub skater_m_hair: Item,
-    pub skater_f_hair: Item,
-    pub skater_m_hat_hair: Item,
-    pub skater_f_hat_hair: Item,
-    pub hat: Item,
-    pub hat_logo: Item,
-    pub eyes: Item,
-    pub glasses: Item,
-
-    pub bare_torso: Item,
-    pub skater_m_torso: Item,
-    pub skater_f_torso: Item,
-    pub front_logo: Item,
-    pub back_logo: Item,
-    pub skater_m_hands: Item,
-    pub skater_f_hands: Item,
-    pub accessory1: Item,
-    pub accessory2: Item,
-    pub accessory3: Item,
-    pub elbowpads: Item,
-    pub sleeves: Item,
-
-    pub skater_m_backpack: Item,
-    pub skater_f_backpack: Item,
-
-    pub skater_m_legs: Item,
-    pub skater_f_legs: Item,
-    pub skater_m_lower_legs: Item,
-    pub skater_f_lower_legs: Item,
-    pub kneepads: Item,
-
-    pub shoes: Item,
-    pub socks: Item,
-    pub shoe_laces: Item,
-
-    pub board: Item,
-    pub deck_graphic: Item,
-    pub griptape: Item,
-
-    pub left_sleeve_tattoo: Item,
-    pub right_sleeve_tattoo: Item,
-    pub left_forearm_tattoo: Item,
-    pub right_forearm_tattoo: Item,
-    pub left_bicep_tattoo: Item,
-    pub right_bicep_tattoo: Item,
-    pub back_tattoo: Item,
-    pub chest_tattoo: Item,
-    pub left_leg_tattoo: Item,
-    pub right_leg_tattoo: Item,
-
-    pub board_bone_group: Item,
-    pub feet_bone_group: Item,
-    pub hands_bone_group: Item,
-    pub head_bone_group: Item,
-    pub headtop_bone_group: Item,
-    pub jaw_bone_group: Item,
-    pub lower_arm_bone_group: Item,
-    pub lower_leg_bone_group: Item,
-    pub nose_bone_group: Item,
-    pub object_scaling: Item,
-    pub stomach_bone_group: Item,
-    pub torso_bone_group: Item,
-    pub upper_arm_bone_group: Item,
-    pub upper_leg_bone_group: Item,
-
-    pub ped_m_accessories: Item,
-    pub ped_f_accessories: Item,
-}
-
-impl Appearance {
-    pub fn modify(&self, appearance: &mut qb::Structure) {
-        self.body_shape.modify(appearance, id::BODY_SHAPE);
-        self.body.modify(appearance, id::BODY);
-        self.skater_m_head.modify(appearance, id::SKATER_M_HEAD);
-        self.skater_f_head.modify(appearance, id::SKATER_F_HEAD);
-        self.skater_m_hair.modify(appearance, id::SKATER_M_HAIR);
-        self.skater_f_hair.modify(appearance, id::SKATER_F_HAIR);
-        self.skater_m_hat_hair
-            .modify(appearance, id::SKATER_M_HAT_HAIR);
-        self.skater_f_hat_hair
-            .modify(appearance, id::SKATER_F_HAT_HAIR);
-        self.hat.modify(appearance, id::HAT);
-        self.hat_logo.modify(appearance, id::HAT_LOGO);
-        self.eyes.modify(appearance, id::EYES);
-        self.glasses.modify(appearance, id::GLASSES);
-        self.bare_torso.modify(appearance, id::BARE_TORSO);
-        self.skater_m_torso.modify(appearance, id::SKATER_M_TORSO);
-        self.skater_f_torso.modify(appearance, id::SKATER_F_TORSO);
-        self.front_logo.modify(appearance, id::FRONT_LOGO);
-        self.back_logo.modify(appearance, id::BACK_LOGO);
-        self.skater_m_hands.modify(appearance, id::SKATER_M_HANDS);
-        self.skater_f_hands.modify(appearance, id::SKATER_F_HANDS);
-        self.accessory1.modify(appearance, id::ACCESSORY1);
-        self.accessory2.modify(appearance, id::ACCESSORY2);
-        self.accessory3.modify(appearance, id::ACCESSORY3);
-        self.elbowpads.modify(appearance, id::ELBOWPADS);
-        self.sleeves.modify(appearance, id::SLEEVES);
-        self.skater_m_backpack
-            .modify(appearance, id::SKATER_M_BACKPACK);
-        self.skater_f_backpack
-            .modify(appearance, id::SKATER_F_BACKPACK);
-        self.skater_m_legs.modify(appearance, id::SKATER_M_LEGS);
-        self.skater_f_legs.modify(appearance, id::SKATER_F_LEGS);
-        self.skater_m_lower_legs
-            .modify(appearance, id::SKATER_M_LOWER_LEGS);
-        self.skater_f_lower_legs
-            .modify(appearance, id::SKATER_F_LOWER_LEGS);
-        self.kneepads.modify(appearance, id::KNEEPADS);
-        self.shoes.modify(appearance, id::SHOES);
-        self.socks.modify(appearance, id::SOCKS);
-        self.shoe_laces.modify(appearance, id::SHOE_LACES);
-        self.board.modify(appearance, id::BOARD);
-        self.deck_graphic.modify(appearance, id::DECK_GRAPHIC);
-        self.griptape.modify(appearance, id::GRIPTAPE);
-        self.left_sleeve_tattoo
-            .modify(appearance, id::LEFT_SLEEVE_TATTOO);
-        self.right_sleeve_tattoo
-            .modify(appearance, id::RIGHT_SLEEVE_TATTOO);
-        self.left_forearm_tattoo
-            .modify(appearance, id::LEFT_FOREARM_TATTOO);
-        self.right_forearm_tattoo
-            .modify(appearance, id::RIGHT_FOREARM_TATTOO);
-        self.left_bicep_tattoo
-            .modify(appearance, id::LEFT_BICEP_TATTOO);
-        self.right_bicep_tattoo
-            .modify(appearance, id::RIGHT_BICEP_TATTOO);
-        self.back_tattoo.modify(appearance, id::BACK_TATTOO);
-        self.chest_tattoo.modify(appearance, id::CHEST_TATTOO);
-        self.left_leg_tattoo.modify(appearance, id::LEFT_LEG_TATTOO);
-        self.right_leg_tattoo
-            .modify(appearance, id::RIGHT_LEG_TATTOO);
-        self.board_bone_group
-            .modify(appearance, id::BOARD_BONE_GROUP);
-        self.feet_bone_group.modify(appearance, id::FEET_BONE_GROUP);
-        self.hands_bone_group
-            .modify(appearance, id::HANDS_BONE_GROUP);
-        self.head_bone_group.modify(appearance, id::HEAD_BONE_GROUP);
-        self.headtop_bone_group
-            .modify(appearance, id::HEADTOP_BONE_GROUP);
-        self.jaw_bone_group.modify(appearance, id::JAW_BONE_GROUP);
-        self.lower_arm_bone_group
-            .modify(appearance, id::LOWER_ARM_BONE_GROUP);
-        self.lower_leg_bone_group
-            .modify(appearance, id::LOWER_LEG_BONE_GROUP);
-        self.nose_bone_group.modify(appearance, id::NOSE_BONE_GROUP);
-        self.object_scaling.modify(appearance, id::OBJECT_SCALING);
-        self.stomach_bone_group
-            .modify(appearance, id::STOMACH_BONE_GROUP);
-        self.torso_bone_group
-            .modify(appearance, id::TORSO_BONE_GROUP);
-        self.upper_arm_bone_group
-            .modify(appearance, id::UPPER_ARM_BONE_GROUP);
-        self.upper_leg_bone_group
-            .modify(appearance, id::UPPER_LEG_BONE_GROUP);
-        self.ped_m_accessories
-            .modify(appearance, id::PED_M_ACCESSORIES);
-        self.ped_f_accessories
-            .modify(appearance, id::PED_F_ACCESSORIES);
+impl Extend<PathItem> for Data {
+    fn extend<T: IntoIterator<Item = PathItem>>(&mut self, items: T) {
+        for item in items {
+            self.set(item.path, item.item);
+        }
     }
 }
 
-impl TryFrom<Box<qb::Structure>> for Appearance {
+impl TryFrom<Box<qb::Structure>> for Data {
     type Error = Error;
 
-    fn try_from(structure: Box<qb::Structure>) -> Result<Self> {
-        Ok(Self {
-            body_shape: structure.get(id::BODY_SHAPE).cloned().into(),
-            body: structure.get(id::BODY).cloned().into(),
-            skater_m_head: structure.get(id::SKATER_M_HEAD).cloned().into(),
-            skater_f_head: structure.get(id::SKATER_F_HEAD).cloned().into(),
-            skater_m_hair: structure.get(id::SKATER_M_HAIR).cloned().into(),
-            skater_f_hair: structure.get(id::SKATER_F_HAIR).cloned().into(),
-            skater_m_hat_hair: structure.get(id::SKATER_M_HAT_HAIR).cloned().into(),
-            skater_f_hat_hair: structure.get(id::SKATER_F_HAT_HAIR).cloned().into(),
-            hat: structure.get(id::HAT).cloned().into(),
-            hat_logo: structure.get(id::HAT_LOGO).cloned().into(),
-            eyes: structure.get(id::EYES).cloned().into(),
-            glasses: structure.get(id::GLASSES).cloned().into(),
-            bare_torso: structure.get(id::BARE_TORSO).cloned().into(),
-            skater_m_torso: structure.get(id::SKATER_M_TORSO).cloned().into(),
-            skater_f_torso: structure.get(id::SKATER_F_TORSO).cloned().into(),
-            front_logo: structure.get(id::FRONT_LOGO).cloned().into(),
-            back_logo: structure.get(id::BACK_LOGO).cloned().into(),
-            skater_m_hands: structure.get(id::SKATER_M_HANDS).cloned().into(),
-            skater_f_hands: structure.get(id::SKATER_F_HANDS).cloned().into(),
-            accessory1: structure.get(id::ACCESSORY1).cloned().into(),
-            accessory2: structure.get(id::ACCESSORY2).cloned().into(),
-            accessory3: structure.get(id::ACCESSORY3).cloned().into(),
-            elbowpads: structure.get(id::ELBOWPADS).cloned().into(),
-            sleeves: structure.get(id::SLEEVES).cloned().into(),
-            skater_m_backpack: structure.get(id::SKATER_M_BACKPACK).cloned().into(),
-            skater_f_backpack: structure.get(id::SKATER_F_BACKPACK).cloned().into(),
-            skater_m_legs: structure.get(id::SKATER_M_LEGS).cloned().into(),
-            skater_f_legs: structure.get(id::SKATER_F_LEGS).cloned().into(),
-            skater_m_lower_legs: structure.get(id::SKATER_M_LOWER_LEGS).cloned().into(),
-            skater_f_lower_legs: structure.get(id::SKATER_F_LOWER_LEGS).cloned().into(),
-            kneepads: structure.get(id::KNEEPADS).cloned().into(),
-            shoes: structure.get(id::SHOES).cloned().into(),
-            socks: structure.get(id::SOCKS).cloned().into(),
-            shoe_laces: structure.get(id::SHOE_LACES).cloned().into(),
-            board: structure.get(id::BOARD).cloned().into(),
-            deck_graphic: structure.get(id::DECK_GRAPHIC).cloned().into(),
-            griptape: structure.get(id::GRIPTAPE).cloned().into(),
-            left_sleeve_tattoo: structure.get(id::LEFT_SLEEVE_TATTOO).cloned().into(),
-            right_sleeve_tattoo: structure.get(id::RIGHT_SLEEVE_TATTOO).cloned().into(),
-            left_forearm_tattoo: structure.get(id::LEFT_FOREARM_TATTOO).cloned().into(),
-            right_forearm_tattoo: structure.get(id::RIGHT_FOREARM_TATTOO).cloned().into(),
-            left_bicep_tattoo: structure.get(id::LEFT_BICEP_TATTOO).cloned().into(),
-            right_bicep_tattoo: structure.get(id::RIGHT_BICEP_TATTOO).cloned().into(),
-            back_tattoo: structure.get(id::BACK_TATTOO).cloned().into(),
-            chest_tattoo: structure.get(id::CHEST_TATTOO).cloned().into(),
-            left_leg_tattoo: structure.get(id::LEFT_LEG_TATTOO).cloned().into(),
-            right_leg_tattoo: structure.get(id::RIGHT_LEG_TATTOO).cloned().into(),
-            board_bone_group: structure.get(id::BOARD_BONE_GROUP).cloned().into(),
-            feet_bone_group: structure.get(id::FEET_BONE_GROUP).cloned().into(),
-            hands_bone_group: structure.get(id::HANDS_BONE_GROUP).cloned().into(),
-            head_bone_group: structure.get(id::HEAD_BONE_GROUP).cloned().into(),
-            headtop_bone_group: structure.get(id::HEADTOP_BONE_GROUP).cloned().into(),
-            jaw_bone_group: structure.get(id::JAW_BONE_GROUP).cloned().into(),
-            lower_arm_bone_group: structure.get(id::LOWER_ARM_BONE_GROUP).cloned().into(),
-            lower_leg_bone_group: structure.get(id::LOWER_LEG_BONE_GROUP).cloned().into(),
-            nose_bone_group: structure.get(id::NOSE_BONE_GROUP).cloned().into(),
-            object_scaling: structure.get(id::OBJECT_SCALING).cloned().into(),
-            stomach_bone_group: structure.get(id::STOMACH_BONE_GROUP).cloned().into(),
-            torso_bone_group: structure.get(id::TORSO_BONE_GROUP).cloned().into(),
-            upper_arm_bone_group: structure.get(id::UPPER_ARM_BONE_GROUP).cloned().into(),
-            upper_leg_bone_group: structure.get(id::UPPER_LEG_BONE_GROUP).cloned().into(),
-            ped_m_accessories: structure.get(id::PED_M_ACCESSORIES).cloned().into(),
-            ped_f_accessories: structure.get(id::PED_F_ACCESSORIES).cloned().into(),
-        })
-    }
-}
-
-#[derive(Debug, Clone, Default)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct StorySkater {
-    pub tricks: Item,
-}
-
-impl TryFrom<Box<qb::Structure>> for StorySkater {
-    type Error = Error;
+    fn try_from(data: Box<qb::Structure>) -> Result<Self> {
+        let mut items = vec![];
+
+        for (parent, known) in known_parts() {
+            let structure = match data.get_path(&parent, &()) {
+                Some(qb::Value::Structure(structure) | qb::Value::StructurePointer(structure)) => {
+                    structure
+                }
+                _ => return Err(qb::Error::PathNotFound(parent.to_string()).into()),
+            };
+
+            items.extend(structure.iter().map(|symbol| PathItem {
+                path: parent.join(qb::Segment::Id(symbol.id)),
+                item: Item::Present(symbol.clone()),
+            }));
+
+            items.extend(
+                known
+                    .into_iter()
+                    .filter(|id| structure.get(*id).is_none())
+                    .map(|id| PathItem {
+                        path: parent.join(qb::Segment::Id(id)),
+                        item: Item::Vacant,
+                    }),
+            );
+        }
 
-    fn try_from(structure: Box<qb::Structure>) -> Result<Self> {
-        Ok(Self {
-            tricks: structure.get(id::TRICKS).cloned().into(),
-        })
+        Ok(Self(items))
     }
 }
 
-impl StorySkater {
-    pub fn modify(&self, story_skater: &mut Box<qb::Structure>) {
-        self.tricks.modify(story_skater, id::TRICKS);
-    }
+macro_rules! parts {
+    ($($parent:ident: [$($part:ident => $id:ident),* $(,)?]),* $(,)?) => {
+        /// The structures read into [`Data`], and the ids of the parts known to be in them.
+        fn known_parts() -> Vec<(qb::Path, Vec<qb::Id>)> {
+            vec![$(($parent(), vec![$(id::$id),*])),*]
+        }
+
+        impl Data {
+            $($(
+                pub fn $part(&self) -> Option<&Item> {
+                    self.get(&$parent().join(qb::Segment::Id(id::$id)))
+                }
+            )*)*
+        }
+    };
+}
+
+parts! {
+    appearance_path: [
+        body_shape => BODY_SHAPE,
+        body => BODY,
+        skater_m_head => SKATER_M_HEAD,
+        skater_f_head => SKATER_F_HEAD,
+        skater_m_hair => SKATER_M_HAIR,
+        skater_f_hair => SKATER_F_HAIR,
+        skater_m_hat_hair => SKATER_M_HAT_HAIR,
+        skater_f_hat_hair => SKATER_F_HAT_HAIR,
+        hat => HAT,
+        hat_logo => HAT_LOGO,
+        eyes => EYES,
+        glasses => GLASSES,
+        bare_torso => BARE_TORSO,
+        skater_m_torso => SKATER_M_TORSO,
+        skater_f_torso => SKATER_F_TORSO,
+        front_logo => FRONT_LOGO,
+        back_logo => BACK_LOGO,
+        skater_m_hands => SKATER_M_HANDS,
+        skater_f_hands => SKATER_F_HANDS,
+        accessory1 => ACCESSORY1,
+        accessory2 => ACCESSORY2,
+        accessory3 => ACCESSORY3,
+        elbowpads => ELBOWPADS,
+        sleeves => SLEEVES,
+        skater_m_backpack => SKATER_M_BACKPACK,
+        skater_f_backpack => SKATER_F_BACKPACK,
+        skater_m_legs => SKATER_M_LEGS,
+        skater_f_legs => SKATER_F_LEGS,
+        skater_m_lower_legs => SKATER_M_LOWER_LEGS,
+        skater_f_lower_legs => SKATER_F_LOWER_LEGS,
+        kneepads => KNEEPADS,
+        shoes => SHOES,
+        socks => SOCKS,
+        shoe_laces => SHOE_LACES,
+        board => BOARD,
+        deck_graphic => DECK_GRAPHIC,
+        griptape => GRIPTAPE,
+        left_sleeve_tattoo => LEFT_SLEEVE_TATTOO,
+        right_sleeve_tattoo => RIGHT_SLEEVE_TATTOO,
+        left_forearm_tattoo => LEFT_FOREARM_TATTOO,
+        right_forearm_tattoo => RIGHT_FOREARM_TATTOO,
+        left_bicep_tattoo => LEFT_BICEP_TATTOO,
+        right_bicep_tattoo => RIGHT_BICEP_TATTOO,
+        back_tattoo => BACK_TATTOO,
+        chest_tattoo => CHEST_TATTOO,
+        left_leg_tattoo => LEFT_LEG_TATTOO,
+        right_leg_tattoo => RIGHT_LEG_TATTOO,
+        board_bone_group => BOARD_BONE_GROUP,
+        feet_bone_group => FEET_BONE_GROUP,
+        hands_bone_group => HANDS_BONE_GROUP,
+        head_bone_group => HEAD_BONE_GROUP,
+        headtop_bone_group => HEADTOP_BONE_GROUP,
+        jaw_bone_group => JAW_BONE_GROUP,
+        lower_arm_bone_group => LOWER_ARM_BONE_GROUP,
+        lower_leg_bone_group => LOWER_LEG_BONE_GROUP,
+        nose_bone_group => NOSE_BONE_GROUP,
+        object_scaling => OBJECT_SCALING,
+        stomach_bone_group => STOMACH_BONE_GROUP,
+        torso_bone_group => TORSO_BONE_GROUP,
+        upper_arm_bone_group => UPPER_ARM_BONE_GROUP,
+        upper_leg_bone_group => UPPER_LEG_BONE_GROUP,
+        ped_m_accessories => PED_M_ACCESSORIES,
+        ped_f_accessories => PED_F_ACCESSORIES,
+    ],
+    info_path: [
+        trick_mapping => TRICK_MAPPING,
+        specials => SPECIALS,
+    ],
+    story_skater_path: [
+        tricks => TRICKS,
+    ],
 }
@@ -6,6 +6,8 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use cascade_save as save;
+
 use crate::{Error, Result};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -128,11 +130,23 @@ impl Entry {
         Ok(BufReader::new(file))
     }
 
+    /// Truncates the save and writes over it in place. Prefer [`Entry::write_save`], which can't
+    /// leave a partially written save behind.
     pub fn writer(&self) -> Result<impl Write> {
         let file = fs::File::create(&self.filepath())?;
         Ok(BufWriter::new(file))
     }
 
+    /// Replaces the save with `save` using [`write_atomic`], then restores its modification
+    /// time.
+    pub fn write_save(&self, save: &save::Save) -> Result<()> {
+        let mut bytes = vec![];
+        save.write(&mut bytes)?;
+
+        write_atomic(self.filepath(), &bytes)?;
+        self.overwrite_metadata()
+    }
+
     pub fn overwrite_metadata(&self) -> Result<()> {
         let filepath = self.filepath();
 
@@ -151,6 +165,36 @@ impl Entry {
     }
 }
 
+/// Writes `bytes` to `path` without ever leaving it partially written.
+///
+/// The bytes go to a temporary file next to `path` first, which is synced to disk and then
+/// renamed over it. If anything fails, `path` is left as it was.
+pub fn write_atomic(path: impl AsRef<Path>, bytes: &[u8]) -> Result<()> {
+    let path = path.as_ref();
+
+    let filename = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| Error::InvalidSaveFilePath(path.to_path_buf()))?;
+    let temp_path = path.with_file_name(format!(".{}.tmp", filename));
+
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+
+        fs::rename(&temp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    result?;
+
+    Ok(())
+}
+
 pub fn find_entries(dir: impl AsRef<Path>) -> Result<Vec<Entry>> {
     let dir = PathBuf::from(dir.as_ref());
 
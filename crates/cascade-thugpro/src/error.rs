@@ -1,7 +1,10 @@
 use std::{io, path::PathBuf, result};
 
+use cascade_lut as lut;
 use cascade_qb as qb;
 use cascade_save as save;
+#[cfg(feature = "serde")]
+use ron::de::SpannedError;
 
 #[derive(thiserror::Error, Debug, Clone)]
 pub enum Error {
@@ -14,6 +17,22 @@ pub enum Error {
     #[error("save error: {0}")]
     Save(#[from] save::Error),
 
+    #[error("lut error: {0}")]
+    Lut(#[from] lut::Error),
+
+    #[cfg(feature = "serde")]
+    #[error("groups error: {0}")]
+    Groups(#[from] SpannedError),
+
+    #[error("unknown group \"{0}\"")]
+    UnknownGroup(String),
+
+    #[error("invalid trickset: {0}")]
+    InvalidTrickset(String),
+
+    #[error("{0} specials do not fit in the empty special slots")]
+    SpecialsDoNotFit(usize),
+
     #[error("unknown save file extension \"{0}\"")]
     UnknownFileExtension(String),
 
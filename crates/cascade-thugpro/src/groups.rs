@@ -0,0 +1,221 @@
+//! Named groups of symbols that are copied between saves together, e.g. a hat and its logo.
+//!
+//! The groups cascade ships with are bundled, and can be replaced or added to with a
+//! [`GROUPS_FILENAME`] in the cascade dir. Reading them from ron needs the `serde` feature.
+
+#[cfg(feature = "serde")]
+use std::{fs, path::Path};
+
+use cascade_lut as lut;
+use cascade_qb as qb;
+
+use crate::{Error, Result, cas};
+
+pub const GROUPS_FILENAME: &str = "groups.ron";
+
+#[cfg(feature = "serde")]
+const GROUPS: &str = include_str!("../../../assets/groups.ron");
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Definition {
+    name: String,
+    #[serde(default)]
+    label: Option<String>,
+    section: String,
+    paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub name: String,
+    /// What the group is shown as, if not its name.
+    pub label: Option<String>,
+    /// What the group is listed under, e.g. `appearance`.
+    pub section: String,
+    /// The symbols of the group, relative to the save data.
+    pub paths: Vec<qb::Path>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<Definition> for Group {
+    type Error = Error;
+
+    fn try_from(definition: Definition) -> Result<Self> {
+        Ok(Self {
+            name: definition.name,
+            label: definition.label,
+            section: definition.section,
+            paths: definition
+                .paths
+                .iter()
+                .map(|path| path.parse())
+                .collect::<qb::Result<_>>()?,
+        })
+    }
+}
+
+impl Group {
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Items copying the symbols of this group out of `data`.
+    ///
+    /// Symbols missing from `data` become [`cas::Item::Vacant`], so they are removed from the
+    /// saves the items are applied to.
+    pub fn items(
+        &self,
+        data: &qb::Structure,
+        names: &impl qb::Resolve,
+    ) -> Result<Vec<cas::PathItem>> {
+        self.paths
+            .iter()
+            .map(|path| item(path, data, names))
+            .collect()
+    }
+}
+
+/// Finds the symbol at `path`, and the ids that lead to it.
+fn item(path: &qb::Path, data: &qb::Structure, names: &impl qb::Resolve) -> Result<cas::PathItem> {
+    let not_found = || qb::Error::PathNotFound(path.to_string());
+
+    let mut structure = data;
+    let mut ids = vec![];
+
+    let (last, parents) = path.segments().split_last().ok_or_else(not_found)?;
+
+    let candidates = |segment: &qb::Segment| match segment {
+        qb::Segment::Id(id) => Ok(vec![*id]),
+        qb::Segment::Name(name) => Ok(names.resolve(name)),
+        qb::Segment::Index(_) => Err(qb::Error::InvalidPath(
+            path.to_string(),
+            "group paths can not index arrays".to_string(),
+        )),
+    };
+
+    for segment in parents {
+        let candidates = candidates(segment)?;
+        let symbol = structure
+            .iter()
+            .find(|symbol| candidates.contains(&symbol.id))
+            .ok_or_else(not_found)?;
+
+        structure = match &symbol.value {
+            qb::Value::Structure(structure) | qb::Value::StructurePointer(structure) => structure,
+            _ => return Err(not_found().into()),
+        };
+        ids.push(qb::Segment::Id(symbol.id));
+    }
+
+    let candidates = candidates(last)?;
+    let symbol = structure
+        .iter()
+        .find(|symbol| candidates.contains(&symbol.id));
+
+    let (id, item) = match symbol {
+        Some(symbol) => (symbol.id, cas::Item::Present(symbol.clone())),
+        None => (
+            *candidates
+                .first()
+                .ok_or_else(|| qb::Error::UnresolvedName(last.to_string()))?,
+            cas::Item::Vacant,
+        ),
+    };
+    ids.push(qb::Segment::Id(id));
+
+    Ok(cas::PathItem {
+        path: qb::Path::new(ids),
+        item,
+    })
+}
+
+/// Parses a list of groups from ron.
+#[cfg(feature = "serde")]
+pub fn parse(s: &str) -> Result<Vec<Group>> {
+    ron::from_str::<Vec<Definition>>(s)?
+        .into_iter()
+        .map(Group::try_from)
+        .collect()
+}
+
+pub struct Groups {
+    groups: Vec<Group>,
+    names: lut::Compress,
+}
+
+impl Groups {
+    /// Groups whose paths are resolved through the compress lut.
+    pub fn new(groups: Vec<Group>) -> Result<Self> {
+        Ok(Self {
+            groups,
+            names: crate::lut::load_compress()?,
+        })
+    }
+
+    /// The groups bundled with cascade.
+    #[cfg(feature = "serde")]
+    pub fn embedded() -> Result<Self> {
+        Self::new(parse(GROUPS)?)
+    }
+
+    /// The bundled groups, with the groups in `path` over them if it exists.
+    #[cfg(feature = "serde")]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let mut groups = Self::embedded()?;
+
+        if path.is_file() {
+            log::info!("reading groups from {:?}", path);
+            groups.merge(parse(&fs::read_to_string(path)?)?);
+        }
+
+        Ok(groups)
+    }
+
+    /// Adds `groups`, replacing the groups of the same name in place.
+    pub fn merge(&mut self, groups: Vec<Group>) {
+        for group in groups {
+            match self
+                .groups
+                .iter_mut()
+                .find(|other| other.name == group.name)
+            {
+                Some(other) => *other = group,
+                None => self.groups.push(group),
+            }
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Group> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Group> {
+        self.groups.iter()
+    }
+
+    /// The sections of the groups, in the order they first appear.
+    pub fn sections(&self) -> Vec<&str> {
+        let mut sections: Vec<&str> = vec![];
+        for group in &self.groups {
+            if !sections.contains(&group.section.as_str()) {
+                sections.push(&group.section);
+            }
+        }
+        sections
+    }
+
+    pub fn in_section<'a>(&'a self, section: &'a str) -> impl Iterator<Item = &'a Group> {
+        self.groups
+            .iter()
+            .filter(move |group| group.section == section)
+    }
+
+    /// Items copying the symbols of the group called `name` out of `data`.
+    pub fn items(&self, name: &str, data: &qb::Structure) -> Result<Vec<cas::PathItem>> {
+        self.get(name)
+            .ok_or_else(|| Error::UnknownGroup(name.to_string()))?
+            .items(data, &self.names)
+    }
+}
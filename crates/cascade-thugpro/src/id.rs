@@ -1,26 +1,70 @@
+use cascade_crc as crc;
 use cascade_qb as qb;
 
-pub const TOTAL_GOALS_COMPLETE: qb::Id = qb::Id::Checksum(2510147438);
-pub const TOTAL_GOALS_POSSIBLE: qb::Id = qb::Id::Checksum(2841615987);
-pub const TOTAL_SCORE: qb::Id = qb::Id::Checksum(236215964);
-pub const TOTAL_SCORE_POTENTIAL: qb::Id = qb::Id::Checksum(2233141508);
+pub const TOTAL_GOALS_COMPLETE: qb::Id = qb::Id::Checksum(crc::key("total_goals_complete"));
+pub const TOTAL_GOALS_POSSIBLE: qb::Id = qb::Id::Checksum(crc::key("total_goals_possible"));
+pub const TOTAL_SCORE: qb::Id = qb::Id::Checksum(crc::key("total_score"));
+pub const TOTAL_SCORE_POTENTIAL: qb::Id = qb::Id::Checksum(crc::key("total_score_potential"));
 pub const IS_MALE: qb::Id = qb::Id::Compress8(220);
 pub const NAME: qb::Id = qb::Id::Compress8(43);
-pub const FILENAME: qb::Id = qb::Id::Checksum(3287553690);
+pub const FILENAME: qb::Id = qb::Id::Checksum(crc::key("filename"));
 
-pub const CUSTOM_SKATER: qb::Id = qb::Id::Checksum(314551426);
-pub const STORY_SKATER: qb::Id = qb::Id::Checksum(234026056);
+pub const CUSTOM_SKATER: qb::Id = qb::Id::Checksum(crc::key("customskater"));
+pub const STORY_SKATER: qb::Id = qb::Id::Checksum(crc::key("storyskater"));
 
 pub const CUSTOM: qb::Id = qb::Id::Compress8(195);
 
-pub const APPEARANCE: qb::Id = qb::Id::Checksum(1431076207);
-pub const INFO: qb::Id = qb::Id::Checksum(880201384);
+pub const APPEARANCE: qb::Id = qb::Id::Checksum(crc::key("appearance"));
+pub const INFO: qb::Id = qb::Id::Checksum(crc::key("info"));
 
 pub const TRICK_MAPPING: qb::Id = qb::Id::Compress8(61);
 pub const SPECIALS: qb::Id = qb::Id::Compress8(64);
+pub const TRICK_SLOT: qb::Id = qb::Id::Compress8(168);
+pub const TRICK_NAME: qb::Id = qb::Id::Compress8(169);
 
-pub const BODY_SHAPE: qb::Id = qb::Id::Checksum(2166785263);
-pub const BODY: qb::Id = qb::Id::Checksum(609743949);
+pub const AIR_CIRCLE_D: qb::Id = qb::Id::Compress8(65);
+pub const AIR_CIRCLE_DL: qb::Id = qb::Id::Compress8(66);
+pub const AIR_CIRCLE_DR: qb::Id = qb::Id::Compress8(67);
+pub const AIR_CIRCLE_L: qb::Id = qb::Id::Compress8(68);
+pub const AIR_CIRCLE_R: qb::Id = qb::Id::Compress8(69);
+pub const AIR_CIRCLE_U: qb::Id = qb::Id::Compress8(70);
+pub const AIR_CIRCLE_UL: qb::Id = qb::Id::Compress8(71);
+pub const AIR_CIRCLE_UR: qb::Id = qb::Id::Compress8(72);
+pub const AIR_D_D_CIRCLE: qb::Id = qb::Id::Compress8(73);
+pub const AIR_D_D_SQUARE: qb::Id = qb::Id::Compress8(74);
+pub const AIR_L_L_CIRCLE: qb::Id = qb::Id::Compress8(75);
+pub const AIR_L_L_SQUARE: qb::Id = qb::Id::Compress8(76);
+pub const AIR_R_R_CIRCLE: qb::Id = qb::Id::Compress8(77);
+pub const AIR_R_R_SQUARE: qb::Id = qb::Id::Compress8(78);
+pub const AIR_SQUARE_D: qb::Id = qb::Id::Compress8(79);
+pub const AIR_SQUARE_DL: qb::Id = qb::Id::Compress8(80);
+pub const AIR_SQUARE_DR: qb::Id = qb::Id::Compress8(81);
+pub const AIR_SQUARE_L: qb::Id = qb::Id::Compress8(82);
+pub const AIR_SQUARE_R: qb::Id = qb::Id::Compress8(83);
+pub const AIR_SQUARE_U: qb::Id = qb::Id::Compress8(84);
+pub const AIR_SQUARE_UL: qb::Id = qb::Id::Compress8(85);
+pub const AIR_SQUARE_UR: qb::Id = qb::Id::Compress8(86);
+pub const AIR_U_U_CIRCLE: qb::Id = qb::Id::Compress8(87);
+pub const AIR_U_U_SQUARE: qb::Id = qb::Id::Compress8(88);
+pub const EXTRA_SLOT1: qb::Id = qb::Id::Compress8(89);
+pub const EXTRA_SLOT2: qb::Id = qb::Id::Compress8(90);
+pub const JUMP_SLOT: qb::Id = qb::Id::Compress8(91);
+pub const LIP_TRIANGLE_D: qb::Id = qb::Id::Compress8(92);
+pub const LIP_TRIANGLE_DL: qb::Id = qb::Id::Compress8(93);
+pub const LIP_TRIANGLE_DR: qb::Id = qb::Id::Compress8(95);
+pub const LIP_TRIANGLE_L: qb::Id = qb::Id::Compress8(97);
+pub const LIP_TRIANGLE_R: qb::Id = qb::Id::Compress8(98);
+pub const LIP_TRIANGLE_U: qb::Id = qb::Id::Compress8(99);
+pub const LIP_TRIANGLE_UL: qb::Id = qb::Id::Compress8(100);
+pub const LIP_TRIANGLE_UR: qb::Id = qb::Id::Compress8(102);
+// The compress table repeats these names on the next id, which saves don't use
+pub const LIP_TRIANGLE_DL_2: qb::Id = qb::Id::Compress8(94);
+pub const LIP_TRIANGLE_DR_2: qb::Id = qb::Id::Compress8(96);
+pub const LIP_TRIANGLE_UL_2: qb::Id = qb::Id::Compress8(101);
+pub const LIP_TRIANGLE_UR_2: qb::Id = qb::Id::Compress8(103);
+
+pub const BODY_SHAPE: qb::Id = qb::Id::Checksum(crc::key("body_shape"));
+pub const BODY: qb::Id = qb::Id::Checksum(crc::key("body"));
 
 pub const SKATER_M_HEAD: qb::Id = qb::Id::Compress8(1);
 pub const SKATER_F_HEAD: qb::Id = qb::Id::Compress8(10);
@@ -30,7 +74,7 @@ pub const SKATER_M_HAT_HAIR: qb::Id = qb::Id::Compress8(16);
 pub const SKATER_F_HAT_HAIR: qb::Id = qb::Id::Compress8(36);
 pub const HAT: qb::Id = qb::Id::Compress8(180);
 pub const HAT_LOGO: qb::Id = qb::Id::Compress8(183);
-pub const EYES: qb::Id = qb::Id::Checksum(1491454825);
+pub const EYES: qb::Id = qb::Id::Checksum(crc::key("eyes"));
 pub const GLASSES: qb::Id = qb::Id::Compress8(179);
 
 pub const BARE_TORSO: qb::Id = qb::Id::Compress8(192);
@@ -59,11 +103,11 @@ pub const SOCKS: qb::Id = qb::Id::Compress8(8);
 pub const SHOE_LACES: qb::Id = qb::Id::Compress8(191);
 
 pub const BOARD: qb::Id = qb::Id::Compress8(185);
-pub const DECK_GRAPHIC: qb::Id = qb::Id::Compress8(30);
+pub const DECK_GRAPHIC: qb::Id = qb::Id::Compress8(187);
 pub const GRIPTAPE: qb::Id = qb::Id::Compress8(186);
 
-pub const LEFT_SLEEVE_TATTOO: qb::Id = qb::Id::Checksum(1037744690);
-pub const RIGHT_SLEEVE_TATTOO: qb::Id = qb::Id::Checksum(2633440348);
+pub const LEFT_SLEEVE_TATTOO: qb::Id = qb::Id::Checksum(crc::key("left_sleeve_tattoo"));
+pub const RIGHT_SLEEVE_TATTOO: qb::Id = qb::Id::Checksum(crc::key("right_sleeve_tattoo"));
 pub const LEFT_FOREARM_TATTOO: qb::Id = qb::Id::Compress8(21);
 pub const RIGHT_FOREARM_TATTOO: qb::Id = qb::Id::Compress8(22);
 pub const LEFT_BICEP_TATTOO: qb::Id = qb::Id::Compress8(23);
@@ -86,9 +130,9 @@ pub const OBJECT_SCALING: qb::Id = qb::Id::Compress8(209);
 pub const STOMACH_BONE_GROUP: qb::Id = qb::Id::Compress8(201);
 pub const TORSO_BONE_GROUP: qb::Id = qb::Id::Compress8(200);
 pub const UPPER_ARM_BONE_GROUP: qb::Id = qb::Id::Compress8(202);
-pub const UPPER_LEG_BONE_GROUP: qb::Id = qb::Id::Checksum(3191687513);
+pub const UPPER_LEG_BONE_GROUP: qb::Id = qb::Id::Checksum(crc::key("upper_leg_bone_group"));
 
-pub const PED_M_ACCESSORIES: qb::Id = qb::Id::Checksum(3856410554);
-pub const PED_F_ACCESSORIES: qb::Id = qb::Id::Checksum(34969625);
+pub const PED_M_ACCESSORIES: qb::Id = qb::Id::Checksum(crc::key("ped_m_accessories"));
+pub const PED_F_ACCESSORIES: qb::Id = qb::Id::Checksum(crc::key("ped_f_accessories"));
 
-pub const TRICKS: qb::Id = qb::Id::Checksum(505871678);
+pub const TRICKS: qb::Id = qb::Id::Checksum(crc::key("tricks"));
@@ -1,10 +1,14 @@
 pub mod cas;
 pub mod entry;
 pub mod error;
+pub mod groups;
 pub mod id;
 pub mod lut;
 pub mod random;
+pub mod trickset;
 
 pub use cas::Cas;
 pub use entry::Entry;
 pub use error::{Error, Result};
+pub use groups::{Group, Groups};
+pub use trickset::Trickset;
@@ -8,10 +8,24 @@ use cascade_save as save;
 use encoding_rs::WINDOWS_1252;
 use rand::seq::IndexedRandom;
 
-use crate::{Cas, Entry, Result, cas, id};
+use crate::{Cas, Entry, Error, Groups, Result, cas, entry, id};
+
+/// The bundles of groups each random skater takes from a different save, if none are given.
+/// The groups of a bundle come from the same save, so e.g. the hat hair matches the hat, and both
+/// elbow and knee pads come with the shirt.
+pub const BUNDLES: [&[&str]; 6] = [
+    &["hair", "hat"],
+    &["eyes", "glasses", "hands", "accessories", "backpack"],
+    &["shirt", "pads"],
+    &["legs"],
+    &["shoes"],
+    &["board"],
+];
 
 pub fn randomize(
     entries: &Vec<Entry>,
+    groups: &Groups,
+    bundles: &[&[&str]],
     output_dir: impl AsRef<Path>,
     name: impl AsRef<str>,
     female: bool,
@@ -19,6 +33,14 @@ pub fn randomize(
     let name = name.as_ref();
     let output_dir = output_dir.as_ref();
 
+    if let Some(unknown) = bundles
+        .iter()
+        .flat_map(|bundle| bundle.iter())
+        .find(|group| groups.get(group).is_none())
+    {
+        return Err(Error::UnknownGroup(unknown.to_string()));
+    }
+
     let mut rng = rand::rng();
 
     let base_entry = entries.choose(&mut rng).unwrap().clone();
@@ -33,8 +55,8 @@ pub fn randomize(
                 .ok()
                 .and_then(|mut reader| save::Save::read(&mut reader).ok())
         })
-        .filter_map(|save| Cas::try_from(save).ok())
-        .filter(|save| match save.summary.is_male {
+        .filter_map(|save| Cas::try_from(save.clone()).ok().map(|cas| (save, cas)))
+        .filter(|(_, cas)| match cas.summary.is_male {
             // Female cas
             cas::Item::Present(qb::Symbol {
                 value: qb::Value::ZeroInt,
@@ -64,35 +86,7 @@ pub fn randomize(
 
     let (filename_bytes, _, _) = WINDOWS_1252.encode(name.as_ref());
 
-    let hat_cas = cases.choose(&mut rng).unwrap();
-    let hat_appearance = &hat_cas.data.custom_skater.custom.appearance;
-
-    let acc_cas = cases.choose(&mut rng).unwrap();
-    let acc_appearance = &acc_cas.data.custom_skater.custom.appearance;
-
-    let shirt_cas = cases.choose(&mut rng).unwrap();
-    let shirt_appearance = &shirt_cas.data.custom_skater.custom.appearance;
-
-    let legs_cas = cases.choose(&mut rng).unwrap();
-    let legs_appearance = &legs_cas.data.custom_skater.custom.appearance;
-
-    let shoes_cas = cases.choose(&mut rng).unwrap();
-    let shoes_appearance = &shoes_cas.data.custom_skater.custom.appearance;
-
-    let board_cas = cases.choose(&mut rng).unwrap();
-    let board_appearance = &board_cas.data.custom_skater.custom.appearance;
-
-    println!("");
-    println!("name: {}", name);
-    println!("base: {}", get_filename(&base_cas));
-    println!("hat: {}", get_filename(&hat_cas));
-    println!("accessories: {}", get_filename(&acc_cas));
-    println!("shirt: {}", get_filename(&shirt_cas));
-    println!("legs: {}", get_filename(&legs_cas));
-    println!("shoes: {}", get_filename(&shoes_cas));
-    println!("board: {}", get_filename(&board_cas));
-
-    let transform = Cas {
+    let mut transform = Cas {
         summary: cas::Summary {
             filename: cas::Item::Present(qb::Symbol {
                 kind: qb::Kind::String,
@@ -101,76 +95,46 @@ pub fn randomize(
             }),
             ..Default::default()
         },
-        data: cas::Data {
-            custom_skater: cas::CustomSkater {
-                custom: cas::Custom {
-                    appearance: cas::Appearance {
-                        skater_m_hair: hat_appearance.skater_m_hair.clone(),
-                        skater_f_hair: hat_appearance.skater_f_hair.clone(),
-                        skater_m_hat_hair: hat_appearance.skater_m_hat_hair.clone(),
-                        skater_f_hat_hair: hat_appearance.skater_f_hat_hair.clone(),
-                        hat: hat_appearance.hat.clone(),
-                        hat_logo: hat_appearance.hat_logo.clone(),
-                        bare_torso: shirt_appearance.bare_torso.clone(),
-                        skater_m_torso: shirt_appearance.skater_m_torso.clone(),
-                        skater_f_torso: shirt_appearance.skater_f_torso.clone(),
-                        front_logo: shirt_appearance.front_logo.clone(),
-                        back_logo: shirt_appearance.back_logo.clone(),
-                        elbowpads: shirt_appearance.elbowpads.clone(),
-                        sleeves: shirt_appearance.shoe_laces.clone(),
-                        eyes: acc_appearance.eyes.clone(),
-                        glasses: acc_appearance.glasses.clone(),
-                        skater_m_hands: acc_appearance.skater_m_hands.clone(),
-                        skater_f_hands: acc_appearance.skater_f_hands.clone(),
-                        accessory1: acc_appearance.accessory1.clone(),
-                        accessory2: acc_appearance.accessory2.clone(),
-                        accessory3: acc_appearance.accessory3.clone(),
-                        ped_m_accessories: acc_appearance.ped_m_accessories.clone(),
-                        ped_f_accessories: acc_appearance.ped_f_accessories.clone(),
-                        skater_m_backpack: acc_appearance.skater_m_backpack.clone(),
-                        skater_f_backpack: acc_appearance.skater_f_backpack.clone(),
-                        skater_m_legs: legs_appearance.skater_m_legs.clone(),
-                        skater_f_legs: legs_appearance.skater_f_legs.clone(),
-                        skater_m_lower_legs: legs_appearance.skater_m_lower_legs.clone(),
-                        skater_f_lower_legs: legs_appearance.skater_f_lower_legs.clone(),
-                        kneepads: legs_appearance.skater_f_lower_legs.clone(),
-                        shoes: shoes_appearance.shoes.clone(),
-                        socks: shoes_appearance.socks.clone(),
-                        shoe_laces: shoes_appearance.shoe_laces.clone(),
-                        board: board_appearance.board.clone(),
-                        deck_graphic: board_appearance.deck_graphic.clone(),
-                        griptape: board_appearance.griptape.clone(),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                },
-            },
-            ..Default::default()
-        },
+        ..Default::default()
     };
 
+    println!("");
+    println!("name: {}", name);
+    println!("base: {}", get_filename(&base_cas));
+
+    for bundle in bundles {
+        let (save, cas) = cases.choose(&mut rng).unwrap();
+
+        println!("{}: {}", bundle.join(", "), get_filename(cas));
+        for group in bundle.iter() {
+            transform.data.extend(groups.items(group, &save.data)?);
+        }
+    }
+
     transform.modify(&mut base_save)?;
 
+    let mut bytes = vec![];
+    base_save.write(&mut bytes)?;
+
     fs::create_dir_all(output_dir)?;
 
     let output_path = PathBuf::from(output_dir).join(format!("{}.SKA", name));
-    fs::File::create(&output_path)?;
-
-    let output_entry = Entry::at_path(&output_path)?;
-    base_save.write(&mut output_entry.writer()?)?;
+    entry::write_atomic(output_path, &bytes)?;
 
     Ok(())
 }
 
 pub fn randomize_bulk(
     entries: &Vec<Entry>,
+    groups: &Groups,
+    bundles: &[&[&str]],
     output_dir: impl AsRef<Path>,
     number: usize,
     female: bool,
 ) -> Result<()> {
     for i in 0..number {
         let name = format!("rand{}", i);
-        randomize(entries, &output_dir, name, female)?;
+        randomize(entries, groups, bundles, &output_dir, name, female)?;
     }
 
     Ok(())
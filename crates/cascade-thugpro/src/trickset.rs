@@ -0,0 +1,608 @@
+//! A typed view of the trickset of a custom skater, i.e. its `trick_mapping` and `specials`.
+
+use std::{fmt, str::FromStr};
+
+use cascade_crc as crc;
+use cascade_lut::Lut;
+use cascade_qb as qb;
+use cascade_save as save;
+
+use crate::{Cas, Error, Result, cas, id};
+
+/// The trick name of empty special slots, and the slot they are on.
+pub const UNASSIGNED: u32 = crc::key("unassigned");
+
+/// The id of the list of specials inside `specials`.
+const SPECIALS_LIST: qb::Id = qb::Id::Checksum(0);
+
+/// The special slots of grinds, e.g. `spgrind_d_l_triangle`.
+pub const GRIND_SLOTS: [u32; 12] = [
+    crc::key("spgrind_d_l_triangle"),
+    crc::key("spgrind_d_r_triangle"),
+    crc::key("spgrind_d_u_triangle"),
+    crc::key("spgrind_l_d_triangle"),
+    crc::key("spgrind_l_r_triangle"),
+    crc::key("spgrind_l_u_triangle"),
+    crc::key("spgrind_r_d_triangle"),
+    crc::key("spgrind_r_l_triangle"),
+    crc::key("spgrind_r_u_triangle"),
+    crc::key("spgrind_u_d_triangle"),
+    crc::key("spgrind_u_l_triangle"),
+    crc::key("spgrind_u_r_triangle"),
+];
+
+/// What a button combo or special slot is set to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Trick {
+    /// The checksum of the trick's name, e.g. `Trick_Impossible`.
+    Name(u32),
+    /// A trick referred to by number rather than by name.
+    Index(u8),
+}
+
+impl Trick {
+    fn read(value: &qb::Value) -> Option<Self> {
+        match value {
+            qb::Value::Name(checksum) => Some(Self::Name(*checksum)),
+            qb::Value::U8(index) => Some(Self::Index(*index)),
+            _ => None,
+        }
+    }
+
+    fn symbol(self, id: qb::Id) -> qb::Symbol {
+        let (kind, value) = match self {
+            Self::Name(checksum) => (qb::Kind::Name, qb::Value::Name(checksum)),
+            Self::Index(index) => (qb::Kind::U8, qb::Value::U8(index)),
+        };
+        qb::Symbol { kind, id, value }
+    }
+
+    /// The name of the trick in `lut`, or its checksum or number if it has none.
+    pub fn name(&self, lut: &Lut) -> String {
+        match self {
+            Self::Name(checksum) => name(qb::Id::Checksum(*checksum), lut),
+            Self::Index(index) => format!("{}", index),
+        }
+    }
+}
+
+/// A button combo and the trick it does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mapping {
+    /// e.g. `Air_SquareU`.
+    pub combo: qb::Id,
+    pub trick: Trick,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Special {
+    pub trick: Trick,
+    /// The checksum of the button combo of the slot, e.g. `spair_u_r_square`.
+    pub slot: u32,
+    /// Any other symbols of the slot, kept as they are.
+    pub extra: Vec<qb::Symbol>,
+}
+
+impl Default for Special {
+    fn default() -> Self {
+        Self {
+            trick: Trick::Name(UNASSIGNED),
+            slot: UNASSIGNED,
+            extra: vec![],
+        }
+    }
+}
+
+impl Special {
+    pub fn is_assigned(&self) -> bool {
+        self.slot != UNASSIGNED
+    }
+
+    fn read(value: &qb::Value) -> Result<Self> {
+        let qb::Value::Structure(structure) = value else {
+            return Err(Error::InvalidTrickset(format!(
+                "special is not a structure: {}",
+                value
+            )));
+        };
+
+        let mut trick = None;
+        let mut slot = None;
+        let mut extra = vec![];
+
+        for symbol in structure.iter() {
+            match (symbol.id, &symbol.value) {
+                (id::TRICK_NAME, value) => trick = Trick::read(value),
+                (id::TRICK_SLOT, qb::Value::Name(checksum)) => slot = Some(*checksum),
+                _ => extra.push(symbol.clone()),
+            }
+        }
+
+        match (trick, slot) {
+            (Some(trick), Some(slot)) => Ok(Self { trick, slot, extra }),
+            _ => Err(Error::InvalidTrickset(
+                "special is missing its trick or slot".to_string(),
+            )),
+        }
+    }
+
+    /// The special as the game writes it, with its trick before its slot.
+    fn value(&self) -> qb::Value {
+        let symbols = [
+            self.trick.symbol(id::TRICK_NAME),
+            Trick::Name(self.slot).symbol(id::TRICK_SLOT),
+        ];
+
+        qb::Value::Structure(Box::new(
+            symbols
+                .into_iter()
+                .chain(self.extra.iter().cloned())
+                .collect(),
+        ))
+    }
+}
+
+/// A part of a trickset that can be copied without the rest of it.
+///
+/// Grinds are not among them, as the game keeps no grind combos in a trickset; only their specials
+/// can be copied, with [`Part::GrindSpecials`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Part {
+    /// A single button combo.
+    Combo(qb::Id),
+    /// The square combos in the air.
+    Flips,
+    /// The circle combos in the air.
+    Grabs,
+    /// The triangle combos on a lip.
+    LipTricks,
+    /// The extra slots and the jump slot.
+    Extras,
+    /// Every special slot.
+    Specials,
+    /// The specials on grind slots, which take the place of the grind specials of a save. As
+    /// where they go depends on the save, they are copied with [`Trickset::grind_specials`].
+    GrindSpecials,
+}
+
+impl Part {
+    /// The parts made of button combos.
+    pub const MAPPINGS: [Part; 4] = [Part::Flips, Part::Grabs, Part::LipTricks, Part::Extras];
+
+    /// The name the part is parsed from, which is its id (e.g. `@c8:84`) for a single combo.
+    pub fn name(&self) -> String {
+        match self {
+            Part::Combo(combo) => qb::Segment::Id(*combo).to_string(),
+            Part::Flips => "flips".to_string(),
+            Part::Grabs => "grabs".to_string(),
+            Part::LipTricks => "lip_tricks".to_string(),
+            Part::Extras => "extras".to_string(),
+            Part::Specials => "specials".to_string(),
+            Part::GrindSpecials => "grind_specials".to_string(),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Part::LipTricks => "lip tricks".to_string(),
+            Part::Extras => "extra slots".to_string(),
+            Part::GrindSpecials => "grind specials".to_string(),
+            part => part.name(),
+        }
+    }
+
+    /// The button combos of the part, none for the specials.
+    pub fn combos(&self) -> Vec<qb::Id> {
+        match self {
+            Part::Combo(combo) => vec![*combo],
+            Part::Flips => vec![
+                id::AIR_D_D_SQUARE,
+                id::AIR_L_L_SQUARE,
+                id::AIR_R_R_SQUARE,
+                id::AIR_SQUARE_D,
+                id::AIR_SQUARE_DL,
+                id::AIR_SQUARE_DR,
+                id::AIR_SQUARE_L,
+                id::AIR_SQUARE_R,
+                id::AIR_SQUARE_U,
+                id::AIR_SQUARE_UL,
+                id::AIR_SQUARE_UR,
+                id::AIR_U_U_SQUARE,
+            ],
+            Part::Grabs => vec![
+                id::AIR_CIRCLE_D,
+                id::AIR_CIRCLE_DL,
+                id::AIR_CIRCLE_DR,
+                id::AIR_CIRCLE_L,
+                id::AIR_CIRCLE_R,
+                id::AIR_CIRCLE_U,
+                id::AIR_CIRCLE_UL,
+                id::AIR_CIRCLE_UR,
+                id::AIR_D_D_CIRCLE,
+                id::AIR_L_L_CIRCLE,
+                id::AIR_R_R_CIRCLE,
+                id::AIR_U_U_CIRCLE,
+            ],
+            Part::LipTricks => vec![
+                id::LIP_TRIANGLE_D,
+                id::LIP_TRIANGLE_DL,
+                id::LIP_TRIANGLE_DL_2,
+                id::LIP_TRIANGLE_DR,
+                id::LIP_TRIANGLE_DR_2,
+                id::LIP_TRIANGLE_L,
+                id::LIP_TRIANGLE_R,
+                id::LIP_TRIANGLE_U,
+                id::LIP_TRIANGLE_UL,
+                id::LIP_TRIANGLE_UL_2,
+                id::LIP_TRIANGLE_UR,
+                id::LIP_TRIANGLE_UR_2,
+            ],
+            Part::Extras => vec![id::EXTRA_SLOT1, id::EXTRA_SLOT2, id::JUMP_SLOT],
+            Part::Specials | Part::GrindSpecials => vec![],
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "flips" => Ok(Part::Flips),
+            "grabs" => Ok(Part::Grabs),
+            "lip_tricks" => Ok(Part::LipTricks),
+            "extras" => Ok(Part::Extras),
+            "specials" => Ok(Part::Specials),
+            "grind_specials" => Ok(Part::GrindSpecials),
+            _ => match s.parse::<qb::Path>()?.segments() {
+                [qb::Segment::Id(combo)] => Ok(Part::Combo(*combo)),
+                _ => Err(Error::InvalidTrickset(format!("unknown part \"{}\"", s))),
+            },
+        }
+    }
+}
+
+/// Something in a trickset the game would not expect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// A button combo mapped more than once.
+    DuplicateCombo(qb::Id),
+    /// A special slot used by more than one special.
+    DuplicateSlot(u32),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::DuplicateCombo(combo) => {
+                write!(
+                    f,
+                    "combo {} is mapped more than once",
+                    qb::Segment::Id(*combo)
+                )
+            }
+            Problem::DuplicateSlot(slot) => write!(
+                f,
+                "slot {} has more than one special",
+                qb::Segment::Id(qb::Id::Checksum(*slot))
+            ),
+        }
+    }
+}
+
+/// The button combos of a skater and the tricks they do, and its specials.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trickset {
+    pub mappings: Vec<Mapping>,
+    pub specials: Vec<Special>,
+}
+
+impl TryFrom<&cas::Data> for Trickset {
+    type Error = Error;
+
+    fn try_from(data: &cas::Data) -> Result<Self> {
+        let present = |item: Option<&cas::Item>, id| match item {
+            Some(cas::Item::Present(symbol)) => Ok(symbol.clone()),
+            _ => Err(Error::SymbolNotFound(id)),
+        };
+
+        Self::read(
+            &present(data.trick_mapping(), id::TRICK_MAPPING)?,
+            &present(data.specials(), id::SPECIALS)?,
+        )
+    }
+}
+
+impl Trickset {
+    /// Reads the `trick_mapping` and `specials` symbols of a save.
+    pub fn read(trick_mapping: &qb::Symbol, specials: &qb::Symbol) -> Result<Self> {
+        let mappings = expect_structure(trick_mapping)?
+            .iter()
+            .map(|symbol| match Trick::read(&symbol.value) {
+                Some(trick) => Ok(Mapping {
+                    combo: symbol.id,
+                    trick,
+                }),
+                None => Err(Error::InvalidTrickset(format!(
+                    "combo {} is mapped to {}",
+                    qb::Segment::Id(symbol.id),
+                    symbol.value
+                ))),
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            mappings,
+            specials: read_specials(specials)?,
+        })
+    }
+
+    pub fn get(&self, combo: qb::Id) -> Option<Trick> {
+        self.mappings
+            .iter()
+            .find(|mapping| mapping.combo == combo)
+            .map(|mapping| mapping.trick)
+    }
+
+    /// Maps `combo` to `trick`, replacing the trick it did before.
+    pub fn set(&mut self, combo: qb::Id, trick: Trick) {
+        match self
+            .mappings
+            .iter_mut()
+            .find(|mapping| mapping.combo == combo)
+        {
+            Some(mapping) => mapping.trick = trick,
+            None => self.mappings.push(Mapping { combo, trick }),
+        }
+    }
+
+    /// Removes the mapping of `combo`.
+    pub fn remove(&mut self, combo: qb::Id) {
+        self.mappings.retain(|mapping| mapping.combo != combo);
+    }
+
+    pub fn validate(&self) -> Vec<Problem> {
+        let mut problems = vec![];
+
+        for (i, mapping) in self.mappings.iter().enumerate() {
+            let combo = Problem::DuplicateCombo(mapping.combo);
+            if self.mappings[..i]
+                .iter()
+                .any(|other| other.combo == mapping.combo)
+                && !problems.contains(&combo)
+            {
+                problems.push(combo);
+            }
+        }
+
+        // Empty slots all share the unassigned slot
+        let assigned: Vec<_> = self
+            .specials
+            .iter()
+            .filter(|special| special.is_assigned())
+            .collect();
+        for (i, special) in assigned.iter().enumerate() {
+            let slot = Problem::DuplicateSlot(special.slot);
+            if assigned[..i].iter().any(|other| other.slot == special.slot)
+                && !problems.contains(&slot)
+            {
+                problems.push(slot);
+            }
+        }
+
+        problems
+    }
+
+    pub fn trick_mapping(&self) -> qb::Symbol {
+        qb::Symbol::structure(
+            id::TRICK_MAPPING,
+            Box::new(
+                self.mappings
+                    .iter()
+                    .map(|mapping| mapping.trick.symbol(mapping.combo))
+                    .collect(),
+            ),
+        )
+    }
+
+    pub fn specials(&self) -> qb::Symbol {
+        specials_symbol(&self.specials)
+    }
+
+    /// Items setting the trickset of a save to this one.
+    pub fn items(&self) -> Vec<cas::PathItem> {
+        vec![
+            cas::PathItem {
+                path: cas::info_path().join(qb::Segment::Id(id::TRICK_MAPPING)),
+                item: cas::Item::Present(self.trick_mapping()),
+            },
+            cas::PathItem {
+                path: cas::info_path().join(qb::Segment::Id(id::SPECIALS)),
+                item: cas::Item::Present(self.specials()),
+            },
+        ]
+    }
+
+    /// Items copying `part` of this trickset onto a save, leaving the rest of its trickset alone.
+    ///
+    /// Combos of the part that this trickset does not map become [`cas::Item::Vacant`]. There are
+    /// none for [`Part::GrindSpecials`], see [`Trickset::grind_specials`].
+    pub fn part_items(&self, part: Part) -> Vec<cas::PathItem> {
+        let trick_mapping = cas::info_path().join(qb::Segment::Id(id::TRICK_MAPPING));
+
+        let mut items: Vec<_> = part
+            .combos()
+            .into_iter()
+            .map(|combo| cas::PathItem {
+                path: trick_mapping.join(qb::Segment::Id(combo)),
+                item: self.get(combo).map(|trick| trick.symbol(combo)).into(),
+            })
+            .collect();
+
+        if part == Part::Specials {
+            items.push(cas::PathItem {
+                path: cas::info_path().join(qb::Segment::Id(id::SPECIALS)),
+                item: cas::Item::Present(self.specials()),
+            });
+        }
+
+        items
+    }
+
+    /// The specials of this trickset on grind slots, to copy onto saves in place of theirs.
+    pub fn grind_specials(&self) -> SlotSpecials {
+        SlotSpecials {
+            slots: GRIND_SLOTS.to_vec(),
+            specials: self
+                .specials
+                .iter()
+                .filter(|special| GRIND_SLOTS.contains(&special.slot))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Shows the trickset with the combos and tricks named by `lut`.
+    pub fn display<'a>(&'a self, lut: &'a Lut) -> Display<'a> {
+        Display {
+            trickset: self,
+            lut,
+        }
+    }
+}
+
+/// Specials to put on a save in place of the ones it has on `slots`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlotSpecials {
+    pub slots: Vec<u32>,
+    pub specials: Vec<Special>,
+}
+
+impl SlotSpecials {
+    /// An item setting the specials of `save` to its own with the slots emptied, and these
+    /// specials on its empty slots.
+    ///
+    /// The save is taken as `transform` leaves it, so the item can be added to `transform`
+    /// without undoing the specials it copies.
+    pub fn item(&self, transform: &Cas, save: &save::Save) -> Result<cas::PathItem> {
+        let mut save = save.clone();
+        transform.modify(&mut save)?;
+
+        let data = Cas::try_from(save)?.data;
+        let Some(cas::Item::Present(specials)) = data.specials() else {
+            return Err(Error::SymbolNotFound(id::SPECIALS));
+        };
+        let mut specials = read_specials(specials)?;
+
+        for special in specials
+            .iter_mut()
+            .filter(|special| self.slots.contains(&special.slot))
+        {
+            *special = Special::default();
+        }
+
+        let mut empty: Vec<_> = specials
+            .iter_mut()
+            .filter(|special| !special.is_assigned())
+            .collect();
+        if empty.len() < self.specials.len() {
+            return Err(Error::SpecialsDoNotFit(self.specials.len() - empty.len()));
+        }
+        for (special, new) in empty.iter_mut().zip(&self.specials) {
+            **special = new.clone();
+        }
+
+        Ok(cas::PathItem {
+            path: cas::info_path().join(qb::Segment::Id(id::SPECIALS)),
+            item: cas::Item::Present(specials_symbol(&specials)),
+        })
+    }
+}
+
+pub struct Display<'a> {
+    trickset: &'a Trickset,
+    lut: &'a Lut,
+}
+
+impl fmt::Display for Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "trick_mapping")?;
+        for mapping in &self.trickset.mappings {
+            writeln!(
+                f,
+                "  {}: {}",
+                name(mapping.combo, self.lut),
+                mapping.trick.name(self.lut)
+            )?;
+        }
+
+        writeln!(f, "specials")?;
+        for (i, special) in self.trickset.specials.iter().enumerate() {
+            match special.is_assigned() {
+                true => writeln!(
+                    f,
+                    "  {}. {}: {}",
+                    i + 1,
+                    name(qb::Id::Checksum(special.slot), self.lut),
+                    special.trick.name(self.lut)
+                )?,
+                false => writeln!(f, "  {}. unassigned", i + 1)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn name(id: qb::Id, lut: &Lut) -> String {
+    let name = match id {
+        qb::Id::Checksum(checksum) => lut.checksum.lookup(checksum),
+        qb::Id::Compress8(i) => lut.compress.lookup8(i),
+        qb::Id::Compress16(i) => lut.compress.lookup16(i),
+        qb::Id::None => None,
+    };
+
+    name.cloned()
+        .unwrap_or_else(|| qb::Segment::Id(id).to_string())
+}
+
+fn read_specials(specials: &qb::Symbol) -> Result<Vec<Special>> {
+    match expect_structure(specials)?.get(SPECIALS_LIST) {
+        Some(qb::Symbol {
+            value: qb::Value::Array(_, values),
+            ..
+        }) => values.iter().map(Special::read).collect(),
+        _ => Err(Error::InvalidTrickset(
+            "specials has no list of specials".to_string(),
+        )),
+    }
+}
+
+fn specials_symbol(specials: &[Special]) -> qb::Symbol {
+    let list = qb::Symbol {
+        kind: qb::Kind::Array,
+        id: SPECIALS_LIST,
+        value: qb::Value::Array(
+            qb::Kind::Structure,
+            specials.iter().map(Special::value).collect(),
+        ),
+    };
+
+    qb::Symbol::structure(id::SPECIALS, Box::new(qb::Structure::new(vec![list])))
+}
+
+fn expect_structure(symbol: &qb::Symbol) -> Result<&qb::Structure> {
+    match &symbol.value {
+        qb::Value::Structure(structure) => Ok(structure),
+        value => Err(Error::ExpectedStructure(
+            qb::Segment::Id(symbol.id).to_string(),
+            value.clone(),
+        )),
+    }
+}
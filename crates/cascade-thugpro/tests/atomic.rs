@@ -0,0 +1,65 @@
+use std::fs;
+
+use cascade_qb as qb;
+use cascade_save as save;
+use cascade_thugpro as thugpro;
+mod common;
+
+fn copy_entry(name: &str) -> thugpro::Entry {
+    let entry = &common::entries()[0];
+    let path = common::output_dir().join(format!("{}.SKA", name));
+    fs::copy(entry.filepath(), &path).expect("could not copy save");
+
+    thugpro::Entry::at_path(&path).expect("could not load entry")
+}
+
+#[test]
+fn write_save_replaces_save() {
+    let entry = copy_entry("atomic_replace");
+    let original_mtime = filetime::FileTime::from_last_modification_time(entry.metadata());
+
+    let mut save = save::Save::read(&mut entry.reader().unwrap()).expect("could not load save");
+    save.data.insert(qb::Symbol {
+        kind: qb::Kind::Integer,
+        id: qb::Id::Checksum(1234),
+        value: qb::Value::I32(5),
+    });
+
+    entry.write_save(&save).expect("could not write save");
+
+    let mut expected = vec![];
+    save.write(&mut expected).expect("could not write save");
+    assert_eq!(fs::read(entry.filepath()).unwrap(), expected);
+
+    let metadata = fs::metadata(entry.filepath()).unwrap();
+    assert_eq!(
+        filetime::FileTime::from_last_modification_time(&metadata),
+        original_mtime
+    );
+
+    let leftovers = fs::read_dir(&entry.dir)
+        .unwrap()
+        .filter_map(|file| file.ok())
+        .filter(|file| file.file_name().to_string_lossy().ends_with(".tmp"))
+        .count();
+    assert_eq!(leftovers, 0);
+}
+
+#[test]
+fn failed_write_leaves_save_alone() {
+    let entry = copy_entry("atomic_failure");
+    let original = fs::read(entry.filepath()).unwrap();
+
+    let mut save = save::Save::read(&mut entry.reader().unwrap()).expect("could not load save");
+    save.data.insert(qb::Symbol {
+        kind: qb::Kind::String,
+        id: qb::Id::Checksum(1234),
+        value: qb::Value::String(vec![b'a'; save::SAVE_FILE_SIZE]),
+    });
+
+    assert!(matches!(
+        entry.write_save(&save),
+        Err(thugpro::Error::Save(save::Error::SaveTooLarge { .. }))
+    ));
+    assert_eq!(fs::read(entry.filepath()).unwrap(), original);
+}
@@ -0,0 +1,60 @@
+use cascade_save as save;
+use cascade_thugpro as thugpro;
+mod common;
+
+#[test]
+fn is_applied_after_modify() {
+    let entries = common::entries();
+
+    let source = save::Save::read(&mut entries[0].reader().unwrap()).expect("could not load save");
+    let mut target =
+        save::Save::read(&mut entries[1].reader().unwrap()).expect("could not load save");
+
+    let transform = thugpro::Cas::try_from(source).expect("could not load cas");
+
+    assert!(!transform.is_applied(&target).unwrap());
+
+    transform
+        .modify(&mut target)
+        .expect("could not modify save");
+    assert!(transform.is_applied(&target).unwrap());
+
+    // An empty transform changes nothing, so it is always applied
+    assert!(thugpro::Cas::default().is_applied(&target).unwrap());
+}
+
+#[test]
+fn round_trip_every_symbol() {
+    use cascade_qb as qb;
+    use thugpro::cas;
+
+    for entry in common::entries() {
+        let save = save::Save::read(&mut entry.reader().unwrap()).expect("could not load save");
+        let cas = thugpro::Cas::try_from(save.clone()).expect("could not load cas");
+
+        // Empty every structure the cas was read from, so only the cas can fill them back in
+        let mut emptied = save.clone();
+        for path in [
+            cas::appearance_path(),
+            cas::info_path(),
+            cas::story_skater_path(),
+        ] {
+            match emptied.data.get_path_mut(&path, &()) {
+                Some(qb::Value::Structure(structure)) => **structure = qb::Structure::new(vec![]),
+                value => panic!(
+                    "{}: expected structure at {}, got {:?}",
+                    entry.name, path, value
+                ),
+            }
+        }
+
+        cas.modify(&mut emptied).expect("could not modify save");
+
+        assert_eq!(
+            emptied.data.raw_bytes().unwrap(),
+            save.data.raw_bytes().unwrap(),
+            "{}",
+            entry.name
+        );
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use std::{env, fs, path::PathBuf};
 
 use cascade_thugpro as thugpro;
@@ -0,0 +1,48 @@
+use cascade_qb as qb;
+use cascade_save as save;
+mod common;
+
+fn check_patch(name: &str, old: &qb::Structure, new: &qb::Structure) -> bool {
+    let patch = qb::diff(old, new);
+
+    let mut patched = old.clone();
+    if let Err(err) = patch.apply(&mut patched) {
+        println!("result for {}: could not apply patch: {}", name, err);
+        return false;
+    }
+
+    let remaining = qb::diff(&patched, new);
+    if !remaining.is_empty() {
+        println!("result for {}: {} changes remaining", name, remaining.len());
+        print!("{}", remaining);
+    }
+
+    remaining.is_empty()
+}
+
+#[test]
+fn diff_and_apply() {
+    let saves = common::entries()
+        .iter()
+        .take(40)
+        .map(|entry| {
+            let save = save::Save::read(&mut entry.reader().unwrap()).expect("could not load save");
+            (entry.filename(), save)
+        })
+        .collect::<Vec<_>>();
+
+    let mut all_passed = true;
+
+    for pair in saves.windows(2) {
+        let (old_name, old) = &pair[0];
+        let (new_name, new) = &pair[1];
+        let name = format!("{} -> {}", old_name, new_name);
+
+        assert!(qb::diff(&old.data, &old.data).is_empty());
+
+        all_passed &= check_patch(&name, &old.summary, &new.summary);
+        all_passed &= check_patch(&name, &old.data, &new.data);
+    }
+
+    assert!(all_passed)
+}
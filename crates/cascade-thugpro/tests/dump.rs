@@ -0,0 +1,136 @@
+use cascade_dump as dump;
+use cascade_lut::{self as lut, Lut};
+use cascade_qb as qb;
+use cascade_save as save;
+use cascade_thugpro as thugpro;
+mod common;
+
+#[test]
+fn dump_and_build() {
+    let lut = Lut {
+        checksum: lut::Checksum::load().expect("could not load checksum lut"),
+        compress: thugpro::lut::load_compress().expect("could not load compress lut"),
+    };
+
+    let mut all_passed = true;
+
+    for entry in common::entries().iter().take(40) {
+        let save = save::Save::read(&mut entry.reader().unwrap()).expect("could not load save");
+        let dump = dump::Save::new(&save, &lut);
+
+        let mut expected = vec![];
+        save.write(&mut expected).expect("could not write save");
+
+        for format in [dump::Format::Ron, dump::Format::Json, dump::Format::Toml] {
+            let mut contents = vec![];
+            format.write(&dump, &mut contents).expect("could not dump");
+
+            let parsed = format
+                .parse(std::str::from_utf8(&contents).expect("dump is not utf-8"))
+                .expect("could not parse dump");
+            let built = save::Save::try_from(&parsed).expect("could not build save");
+
+            let mut actual = vec![];
+            built
+                .write(&mut actual)
+                .expect("could not write built save");
+
+            if expected != actual {
+                println!(
+                    "result for {} ({}): built save differs",
+                    entry.filename(),
+                    format
+                );
+                all_passed = false;
+            }
+        }
+
+        for format in [dump::Format::JsonLines, dump::Format::Tree] {
+            let mut contents = vec![];
+            format.write(&dump, &mut contents).expect("could not dump");
+
+            assert!(matches!(format.parse(""), Err(dump::Error::Unreadable(_))));
+        }
+    }
+
+    assert!(all_passed)
+}
+
+#[test]
+fn dump_nan() {
+    let lut = Lut {
+        checksum: lut::Checksum(Default::default()),
+        compress: thugpro::lut::load_compress().expect("could not load compress lut"),
+    };
+
+    // A quiet NaN with a payload, which formats tend to write as a plain NaN or null
+    let nan = f32::from_bits(0x7fc00001);
+    let mut save = save::Save::read(&mut common::entries()[0].reader().unwrap()).unwrap();
+    for (i, value) in [
+        qb::Value::F32(nan),
+        qb::Value::Pair(nan, -0.0),
+        qb::Value::Vector(1.5, nan, f32::INFINITY),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        save.data.insert(qb::Symbol {
+            kind: value.kind(),
+            id: qb::Id::Checksum(i as u32 + 1),
+            value,
+        });
+    }
+
+    let mut expected = vec![];
+    save.write(&mut expected).expect("could not write save");
+    let dump = dump::Save::new(&save, &lut);
+
+    for format in dump::Format::ALL {
+        let mut contents = vec![];
+        format.write(&dump, &mut contents).expect("could not dump");
+
+        let parsed = match format.parse(std::str::from_utf8(&contents).expect("dump is not utf-8"))
+        {
+            Ok(parsed) => parsed,
+            Err(dump::Error::Unreadable(_)) => continue,
+            Err(err) => panic!("could not parse {} dump: {}", format, err),
+        };
+
+        let mut actual = vec![];
+        save::Save::try_from(&parsed)
+            .expect("could not build save")
+            .write(&mut actual)
+            .expect("could not write built save");
+
+        assert!(expected == actual, "{} dump lost a float", format);
+    }
+}
+
+#[test]
+fn hex_dump() {
+    let lut = Lut {
+        checksum: lut::Checksum::load().expect("could not load checksum lut"),
+        compress: thugpro::lut::load_compress().expect("could not load compress lut"),
+    };
+
+    let entry = &common::entries()[0];
+    let bytes = std::fs::read(entry.filepath()).expect("could not read save");
+
+    let mut contents = vec![];
+    dump::hex::write(&bytes, &lut, &mut contents).expect("could not write hex dump");
+    let contents = String::from_utf8(contents).expect("hex dump is not utf-8");
+
+    assert!(contents.starts_with("00000000  "));
+    assert!(contents.contains("header.summary_size"));
+    assert!(contents.contains("compress8"));
+    assert!(contents.contains("padding: "));
+    assert!(!contents.contains("error: "));
+
+    // A truncated save is dumped up to the point it breaks
+    let mut contents = vec![];
+    dump::hex::write(&bytes[..100], &lut, &mut contents).expect("could not write hex dump");
+    let contents = String::from_utf8(contents).expect("hex dump is not utf-8");
+
+    assert!(contents.contains("header.version"));
+    assert!(contents.contains("error: "));
+}
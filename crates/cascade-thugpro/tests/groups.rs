@@ -0,0 +1,119 @@
+#![cfg(feature = "serde")]
+
+use std::fs;
+
+use cascade_save as save;
+use cascade_thugpro as thugpro;
+mod common;
+
+fn read(entry: &thugpro::Entry) -> save::Save {
+    save::Save::read(&mut entry.reader().unwrap()).expect("could not load save")
+}
+
+fn data(save: &save::Save) -> thugpro::cas::Data {
+    thugpro::Cas::try_from(save.clone())
+        .expect("could not load cas")
+        .data
+}
+
+#[test]
+fn copy_group() {
+    let entries = common::entries();
+    let groups = thugpro::Groups::embedded().expect("could not load groups");
+
+    let source = read(&entries[0]);
+    let mut target = read(&entries[1]);
+    let hat = format!("{:?}", data(&target).hat());
+
+    let mut transform = thugpro::Cas::default();
+    transform.data.extend(
+        groups
+            .items("board", &source.data)
+            .expect("could not copy group"),
+    );
+    transform
+        .modify(&mut target)
+        .expect("could not modify save");
+
+    let source = data(&source);
+    let target = data(&target);
+    assert_eq!(
+        format!("{:?}", source.board()),
+        format!("{:?}", target.board())
+    );
+    assert_eq!(
+        format!("{:?}", source.griptape()),
+        format!("{:?}", target.griptape())
+    );
+    assert_eq!(hat, format!("{:?}", target.hat()));
+}
+
+#[test]
+fn every_group_resolves() {
+    let groups = thugpro::Groups::embedded().expect("could not load groups");
+
+    for entry in common::entries() {
+        let save = read(&entry);
+
+        for group in groups.iter() {
+            let mut transform = thugpro::Cas::default();
+            transform.data.extend(
+                groups
+                    .items(&group.name, &save.data)
+                    .unwrap_or_else(|err| panic!("{}: {}: {}", entry.name, group.name, err)),
+            );
+
+            // Copying a save onto itself changes nothing
+            assert!(
+                transform.is_applied(&save).unwrap(),
+                "{}: {}",
+                entry.name,
+                group.name
+            );
+        }
+    }
+}
+
+#[test]
+fn user_groups() {
+    let path = common::output_dir().join(thugpro::groups::GROUPS_FILENAME);
+    fs::write(
+        &path,
+        r#"[
+            (
+                name: "hat",
+                section: "appearance",
+                paths: ["customskater.custom.appearance.hat"],
+            ),
+            (
+                name: "chest_tattoo",
+                label: Some("chest tattoo"),
+                section: "tattoos",
+                paths: ["customskater.custom.appearance.@c8:19"],
+            ),
+        ]"#,
+    )
+    .unwrap();
+
+    let embedded = thugpro::Groups::embedded().unwrap();
+    let groups = thugpro::Groups::load(&path).expect("could not load groups");
+
+    assert_eq!(groups.iter().count(), embedded.iter().count() + 1);
+    assert_eq!(groups.get("hat").unwrap().paths.len(), 1);
+    assert_eq!(groups.get("chest_tattoo").unwrap().label(), "chest tattoo");
+    assert_eq!(groups.sections().last(), Some(&"tattoos"));
+
+    // Groups replaced by the user keep their place
+    let position = |groups: &thugpro::Groups| groups.iter().position(|group| group.name == "hat");
+    assert_eq!(position(&groups), position(&embedded));
+
+    assert!(matches!(
+        groups.items(
+            "nope",
+            &save::Save::read(&mut common::entries()[0].reader().unwrap())
+                .unwrap()
+                .data
+        ),
+        Err(thugpro::Error::UnknownGroup(_))
+    ));
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use cascade_lut::{self as lut, Lut};
+use cascade_qb as qb;
+use cascade_save as save;
+use cascade_thugpro::{self as thugpro, cas};
+mod common;
+
+fn lut() -> Lut {
+    Lut {
+        checksum: lut::Checksum(HashMap::new()),
+        compress: thugpro::lut::load_compress().expect("could not load compress lut"),
+    }
+}
+
+#[test]
+fn get_path_matches_cas() {
+    let lut = lut();
+    let hat: qb::Path = "#314551426.custom.#1431076207.hat".parse().unwrap();
+
+    for entry in common::entries() {
+        let save = save::Save::read(&mut entry.reader().unwrap()).expect("could not load save");
+        let by_path = save
+            .data
+            .get_path(&hat, &lut)
+            .map(|value| value.to_string());
+
+        let cas = thugpro::Cas::try_from(save).expect("could not load cas");
+        let by_cas = match cas.data.hat() {
+            Some(cas::Item::Present(symbol)) => Some(symbol.value.to_string()),
+            _ => None,
+        };
+
+        assert_eq!(by_path, by_cas, "hat mismatch for {}", entry.filename());
+    }
+}
+
+#[test]
+fn set_and_remove_path() {
+    let lut = lut();
+    let entry = common::entries()
+        .into_iter()
+        .next()
+        .expect("no saves found");
+    let mut save = save::Save::read(&mut entry.reader().unwrap()).expect("could not load save");
+
+    let appearance: qb::Path = "#314551426.@c8:195.#1431076207".parse().unwrap();
+    let socks = appearance.join(qb::Segment::Name("socks".to_string()));
+
+    save.data.remove_path(&socks, &lut);
+    assert!(save.data.get_path(&socks, &lut).is_none());
+
+    save.data
+        .set_path(&socks, qb::Value::Name(1234), &lut)
+        .expect("could not set socks");
+
+    assert!(matches!(
+        save.data.get_path(&socks, &lut),
+        Some(qb::Value::Name(1234))
+    ));
+    assert!(matches!(
+        save.data
+            .get_path(&appearance.join(qb::Segment::Id(thugpro::id::SOCKS)), &())
+            .cloned(),
+        Some(qb::Value::Name(1234))
+    ));
+}
@@ -0,0 +1,54 @@
+use cascade_save as save;
+mod common;
+
+#[test]
+fn intact_saves_need_no_fixes() {
+    for entry in common::entries().iter().take(10) {
+        let bytes = std::fs::read(entry.filepath()).expect("could not read save");
+        let (_, fixes) = save::repair(&bytes).expect("could not repair save");
+
+        assert!(fixes.is_empty(), "{}: {:?}", entry.filename(), fixes);
+    }
+}
+
+#[test]
+fn repair_restores_damaged_save() {
+    let entry = &common::entries()[0];
+    let bytes = std::fs::read(entry.filepath()).expect("could not read save");
+
+    let mut damaged = bytes.clone();
+    damaged[0] ^= 0xff;
+    damaged[8] ^= 0x01;
+
+    let total_size = i32::from_le_bytes(bytes[12..16].try_into().unwrap()) as usize;
+    damaged.truncate(total_size + 10);
+    damaged.extend_from_slice(b"garbage");
+
+    let (save, fixes) = save::repair(&damaged).expect("could not repair save");
+
+    assert!(fixes.contains(&save::Fix::Checksum {
+        field: "checksum",
+        old: u32::from_le_bytes(damaged[0..4].try_into().unwrap()),
+        new: u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+    }));
+    assert!(fixes.iter().any(|fix| matches!(
+        fix,
+        save::Fix::Size {
+            field: "summary_size",
+            ..
+        }
+    )));
+    assert!(
+        fixes
+            .iter()
+            .any(|fix| matches!(fix, save::Fix::Padding { old: 10, .. }))
+    );
+    assert!(fixes.contains(&save::Fix::TrailingGarbage {
+        offset: total_size + 10,
+        len: 7,
+    }));
+
+    let mut repaired = vec![];
+    save.write(&mut repaired).expect("could not write save");
+    assert_eq!(repaired, bytes);
+}
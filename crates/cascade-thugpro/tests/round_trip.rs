@@ -86,5 +86,67 @@ fn round_trip() {
     assert!(all_passed.load(Ordering::SeqCst))
 }
 
+fn round_trip_bytes(bytes: &[u8]) -> Vec<u8> {
+    let save = save::Save::read(&mut &bytes[..]).expect("could not load save");
+
+    let mut output = vec![];
+    save.write(&mut output).expect("could not write save");
+
+    output
+}
+
+#[test]
+fn round_trip_nonstandard_trailer() {
+    let entry = &common::entries()[0];
+    let bytes = read_entry_bytes(entry);
+    let total_size = i32::from_le_bytes(bytes[12..16].try_into().unwrap()) as usize;
+
+    // Zero padding, like other tools write
+    let mut zeroed = bytes.clone();
+    zeroed[total_size..].fill(0);
+    assert_eq!(round_trip_bytes(&zeroed), zeroed);
+
+    // Truncated padding
+    let truncated = bytes[..total_size + 100].to_vec();
+    assert_eq!(round_trip_bytes(&truncated), truncated);
+
+    // No padding at all
+    let unpadded = bytes[..total_size].to_vec();
+    assert_eq!(round_trip_bytes(&unpadded), unpadded);
+
+    // Normalizing brings back the usual padding
+    let mut save = save::Save::read(&mut &zeroed[..]).expect("could not load save");
+    save.normalize_trailer();
+
+    let mut output = vec![];
+    save.write(&mut output).expect("could not write save");
+    assert_eq!(output, bytes);
+}
+
+#[test]
+fn modified_save_keeps_its_size() {
+    let entry = &common::entries()[0];
+    let bytes = read_entry_bytes(entry);
+    let total_size = i32::from_le_bytes(bytes[12..16].try_into().unwrap()) as usize;
+
+    let mut zeroed = bytes.clone();
+    zeroed[total_size..].fill(0);
+    let truncated = bytes[..total_size + 100].to_vec();
+
+    for bytes in [zeroed, truncated] {
+        let mut save = save::Save::read(&mut &bytes[..]).expect("could not load save");
+        assert!(save.trailer.is_some());
+
+        // Shrink the data, so the trailer no longer lines up with the end of the file
+        let id = save.data.iter().last().unwrap().id;
+        save.data.remove(id);
+
+        let mut output = vec![];
+        save.write(&mut output).expect("could not write save");
+        assert_eq!(output.len(), save::SAVE_FILE_SIZE);
+        assert_eq!(output.last(), Some(&0x69));
+    }
+}
+
 // TODO: round trip test copying identical trickset
 // TODO: round trip test changing filename of files
@@ -0,0 +1,54 @@
+use cascade_qb as qb;
+use cascade_save as save;
+mod common;
+
+#[test]
+fn encoded_len_matches_header() {
+    for entry in common::entries().iter().take(10) {
+        let save = save::Save::read(&mut entry.reader().unwrap()).expect("could not load save");
+
+        let len = save.encoded_len().expect("could not encode save");
+        assert_eq!(len, save.header.total_size as usize);
+        assert_eq!(
+            save.headroom().expect("save should fit"),
+            save::SAVE_FILE_SIZE - len
+        );
+    }
+}
+
+#[test]
+fn oversized_saves_are_not_written() {
+    let entry = &common::entries()[0];
+    let mut save = save::Save::read(&mut entry.reader().unwrap()).expect("could not load save");
+
+    let headroom = save.headroom().expect("save should fit");
+
+    // A string symbol with a checksum id takes 6 bytes besides its contents
+    save.data.insert(qb::Symbol {
+        kind: qb::Kind::String,
+        id: qb::Id::Checksum(1234),
+        value: qb::Value::String(vec![b'a'; headroom - 6]),
+    });
+    assert_eq!(save.headroom().expect("save should just fit"), 0);
+
+    let mut bytes = vec![];
+    save.write(&mut bytes).expect("could not write save");
+    assert_eq!(bytes.len(), save::SAVE_FILE_SIZE);
+
+    if let qb::Value::String(string) = &mut save.data.get_mut(qb::Id::Checksum(1234)).unwrap().value
+    {
+        string.push(b'a');
+    }
+
+    assert!(matches!(
+        save.headroom(),
+        Err(save::Error::SaveTooLarge { len, max }) if len == max + 1
+    ));
+
+    let mut bytes = vec![];
+    assert!(matches!(
+        save.write(&mut bytes),
+        Err(save::Error::SaveTooLarge { .. })
+    ));
+    assert!(bytes.is_empty());
+}
@@ -0,0 +1,36 @@
+use cascade_qb as qb;
+use cascade_save as save;
+use cascade_thugpro as thugpro;
+mod common;
+
+fn round_trip_structure(entry: &thugpro::Entry, structure: &qb::Structure) -> bool {
+    let text = qb::text::to_string(structure);
+
+    let passed = match qb::text::from_str(&text) {
+        Ok(parsed) => parsed.raw_bytes().ok() == structure.raw_bytes().ok(),
+        Err(err) => {
+            println!("could not parse text for {}: {}", entry.filename(), err);
+            false
+        }
+    };
+
+    if !passed {
+        println!("result for {}: fail", entry.filename());
+    }
+
+    passed
+}
+
+#[test]
+fn text_round_trip() {
+    let mut all_passed = true;
+
+    for entry in common::entries() {
+        let save = save::Save::read(&mut entry.reader().unwrap()).expect("could not load save");
+
+        all_passed &= round_trip_structure(&entry, &save.summary);
+        all_passed &= round_trip_structure(&entry, &save.data);
+    }
+
+    assert!(all_passed)
+}
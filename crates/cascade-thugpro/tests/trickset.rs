@@ -0,0 +1,337 @@
+use cascade_crc as crc;
+use cascade_lut::{self as lut, Lut};
+use cascade_qb as qb;
+use cascade_save as save;
+use cascade_thugpro::{
+    self as thugpro, cas, id,
+    trickset::{Part, Problem, Special, Trick, Trickset},
+};
+mod common;
+
+fn read(entry: &thugpro::Entry) -> save::Save {
+    save::Save::read(&mut entry.reader().unwrap()).expect("could not load save")
+}
+
+fn trickset(save: &save::Save) -> Trickset {
+    let cas = thugpro::Cas::try_from(save.clone()).expect("could not load cas");
+    Trickset::try_from(&cas.data).expect("could not read trickset")
+}
+
+fn bytes(symbol: qb::Symbol) -> Vec<u8> {
+    qb::Structure::new(vec![symbol]).raw_bytes().unwrap()
+}
+
+fn slots(trickset: &Trickset) -> Vec<(Trick, u32)> {
+    trickset
+        .specials
+        .iter()
+        .map(|special| (special.trick, special.slot))
+        .collect()
+}
+
+#[test]
+fn read_every_trickset() {
+    for entry in common::entries() {
+        let save = read(&entry);
+        let cas = thugpro::Cas::try_from(save.clone()).unwrap();
+        let trickset =
+            Trickset::try_from(&cas.data).unwrap_or_else(|err| panic!("{}: {}", entry.name, err));
+
+        assert!(!trickset.mappings.is_empty(), "{}", entry.name);
+        assert!(
+            trickset.validate().is_empty(),
+            "{}: {:?}",
+            entry.name,
+            trickset.validate()
+        );
+
+        let Some(cas::Item::Present(trick_mapping)) = cas.data.trick_mapping() else {
+            panic!("{}: no trick mapping", entry.name);
+        };
+        assert_eq!(
+            bytes(trick_mapping.clone()),
+            bytes(trickset.trick_mapping()),
+            "{}",
+            entry.name
+        );
+
+        let reread = Trickset::read(&trickset.trick_mapping(), &trickset.specials()).unwrap();
+        assert_eq!(slots(&trickset), slots(&reread), "{}", entry.name);
+    }
+}
+
+#[test]
+fn write_trickset() {
+    let entries = common::entries();
+    let mut save = read(&entries[0]);
+    let mut trickset = trickset(&save);
+
+    let before = trickset.clone();
+    trickset.set(id::AIR_SQUARE_U, Trick::Index(3));
+    trickset.specials[0] = Special::default();
+
+    let mut transform = thugpro::Cas::default();
+    transform.data.extend(trickset.items());
+    transform.modify(&mut save).expect("could not modify save");
+
+    let after = self::trickset(&save);
+    assert_eq!(after.get(id::AIR_SQUARE_U), Some(Trick::Index(3)));
+    assert!(!after.specials[0].is_assigned());
+    assert_eq!(slots(&after)[1..], slots(&before)[1..]);
+
+    for mapping in before.mappings {
+        if mapping.combo != id::AIR_SQUARE_U {
+            assert_eq!(after.get(mapping.combo), Some(mapping.trick));
+        }
+    }
+}
+
+#[test]
+fn validate_trickset() {
+    let mut trickset = trickset(&read(&common::entries()[0]));
+    assert!(trickset.validate().is_empty());
+
+    let mut mapping = trickset.mappings[0];
+    mapping.trick = Trick::Index(0);
+    trickset.mappings.push(mapping);
+
+    let mut special = Special {
+        slot: crc::key("spair_u_r_square"),
+        ..Default::default()
+    };
+    trickset.specials.push(special.clone());
+    special.trick = Trick::Index(1);
+    trickset.specials.push(special.clone());
+
+    // Empty slots can repeat
+    trickset.specials.push(Special::default());
+    trickset.specials.push(Special::default());
+
+    assert_eq!(
+        trickset.validate(),
+        vec![
+            Problem::DuplicateCombo(mapping.combo),
+            Problem::DuplicateSlot(special.slot)
+        ]
+    );
+}
+
+#[test]
+fn display_trickset() {
+    let lut = Lut {
+        checksum: lut::Checksum::from_names(["Trick_Impossible"]),
+        compress: thugpro::lut::load_compress().expect("could not load compress lut"),
+    };
+
+    let mut trickset = trickset(&read(&common::entries()[0]));
+    trickset.set(id::AIR_SQUARE_U, Trick::Name(crc::key("Trick_Impossible")));
+
+    let display = trickset.display(&lut).to_string();
+    assert!(
+        display.contains("Air_SquareU: Trick_Impossible"),
+        "{}",
+        display
+    );
+}
+
+#[test]
+fn copy_part() {
+    let entries = common::entries();
+    let mut source = trickset(&read(&entries[0]));
+    let mut target = read(&entries[1]);
+
+    source.set(id::AIR_SQUARE_U, Trick::Index(1));
+    source.remove(id::AIR_D_D_SQUARE);
+    source.set(id::AIR_CIRCLE_D, Trick::Index(2));
+    source.set(id::AIR_CIRCLE_DL, Trick::Index(3));
+    source.set(id::LIP_TRIANGLE_D, Trick::Index(4));
+    source.specials[0] = Special::default();
+
+    let mut before = trickset(&target);
+    before.set(id::AIR_D_D_SQUARE, Trick::Index(5));
+    before.set(id::AIR_CIRCLE_DL, Trick::Index(6));
+    before.set(id::LIP_TRIANGLE_D, Trick::Index(7));
+    let mut transform = thugpro::Cas::default();
+    transform.data.extend(before.items());
+    transform
+        .modify(&mut target)
+        .expect("could not modify save");
+
+    let mut transform = thugpro::Cas::default();
+    for part in [Part::Flips, Part::Combo(id::AIR_CIRCLE_D), Part::Specials] {
+        transform.data.extend(source.part_items(part));
+    }
+    transform
+        .modify(&mut target)
+        .expect("could not modify save");
+
+    let after = trickset(&target);
+    assert_eq!(after.get(id::AIR_SQUARE_U), Some(Trick::Index(1)));
+    assert_eq!(after.get(id::AIR_D_D_SQUARE), None);
+    assert_eq!(after.get(id::AIR_CIRCLE_D), Some(Trick::Index(2)));
+    assert_eq!(after.get(id::AIR_CIRCLE_DL), Some(Trick::Index(6)));
+    assert_eq!(after.get(id::LIP_TRIANGLE_D), Some(Trick::Index(7)));
+    assert_eq!(after.mappings.len(), before.mappings.len() - 1);
+    assert!(!after.specials[0].is_assigned());
+    assert_eq!(slots(&after), slots(&source));
+}
+
+fn special(slot: &str, index: u8) -> Special {
+    Special {
+        trick: Trick::Index(index),
+        slot: crc::key(slot),
+        extra: vec![],
+    }
+}
+
+/// Sets the specials of `save` to `specials`, with the rest of its 12 slots unassigned.
+fn set_specials(save: &mut save::Save, specials: &[Special]) -> Trickset {
+    let mut trickset = trickset(save);
+    trickset.specials = specials.to_vec();
+    trickset.specials.resize(12, Special::default());
+
+    let mut transform = thugpro::Cas::default();
+    transform.data.extend(trickset.items());
+    transform.modify(save).expect("could not modify save");
+
+    trickset
+}
+
+/// Applies `transform` to `target` along with the grind specials of `source`.
+fn copy_grind_specials_with(
+    mut transform: thugpro::Cas,
+    source: &Trickset,
+    target: &mut save::Save,
+) -> thugpro::Result<()> {
+    let item = source.grind_specials().item(&transform, target)?;
+    transform.data.extend([item]);
+    transform.modify(target)
+}
+
+#[test]
+fn copy_grind_specials() {
+    let entries = common::entries();
+    let mut source = read(&entries[0]);
+    let mut target = read(&entries[1]);
+
+    let air = special("spair_u_d_square", 1);
+    let grind = special("spgrind_u_r_triangle", 2);
+    let lip = special("splip_d_u_triangle", 3);
+
+    let source = set_specials(&mut source, &[air, grind.clone()]);
+    let before = set_specials(
+        &mut target,
+        &[
+            lip.clone(),
+            special("spgrind_r_l_triangle", 4),
+            special("spgrind_d_l_triangle", 5),
+        ],
+    );
+
+    copy_grind_specials_with(thugpro::Cas::default(), &source, &mut target)
+        .expect("could not copy grind specials");
+
+    let mut expected = vec![lip, grind];
+    expected.resize(12, Special::default());
+
+    let after = trickset(&target);
+    assert_eq!(
+        slots(&after),
+        slots(&Trickset {
+            specials: expected,
+            ..Default::default()
+        })
+    );
+    assert_eq!(after.mappings.len(), before.mappings.len());
+}
+
+#[test]
+fn copy_specials_and_grind_specials() {
+    let entries = common::entries();
+    let mut source = read(&entries[0]);
+    let mut target = read(&entries[1]);
+
+    let source = set_specials(
+        &mut source,
+        &[
+            special("spair_u_d_square", 1),
+            special("spgrind_u_r_triangle", 2),
+            special("splip_d_u_triangle", 3),
+        ],
+    );
+    set_specials(&mut target, &[special("spgrind_r_l_triangle", 4)]);
+
+    let mut transform = thugpro::Cas::default();
+    transform.data.extend(source.part_items(Part::Specials));
+    copy_grind_specials_with(transform, &source, &mut target).expect("could not copy specials");
+
+    // The specials that are not on grind slots are copied too
+    assert_eq!(slots(&trickset(&target)), slots(&source));
+}
+
+#[test]
+fn grind_specials_must_fit() {
+    let entries = common::entries();
+    let mut source = read(&entries[0]);
+    let mut target = read(&entries[1]);
+
+    let source = set_specials(
+        &mut source,
+        &[
+            special("spgrind_u_r_triangle", 1),
+            special("spgrind_r_l_triangle", 2),
+        ],
+    );
+    let full: Vec<_> = (0..11)
+        .map(|i| special(&format!("spair_{}", i), i))
+        .chain([special("spgrind_d_l_triangle", 11)])
+        .collect();
+    set_specials(&mut target, &full);
+
+    assert!(matches!(
+        source
+            .grind_specials()
+            .item(&thugpro::Cas::default(), &target),
+        Err(thugpro::Error::SpecialsDoNotFit(1))
+    ));
+}
+
+#[test]
+fn part_combo_names() {
+    let compress = thugpro::lut::load_compress().expect("could not load compress lut");
+    let names = |part: Part| -> Vec<String> {
+        part.combos()
+            .into_iter()
+            .map(|combo| match combo {
+                qb::Id::Compress8(i) => compress.lookup8(i).cloned().unwrap_or_default(),
+                _ => panic!("{:?} is not compressed", combo),
+            })
+            .collect()
+    };
+
+    for (part, pattern) in [
+        (Part::Flips, "Square"),
+        (Part::Grabs, "Circle"),
+        (Part::LipTricks, "Lip_Triangle"),
+    ] {
+        for name in names(part) {
+            assert!(name.contains(pattern), "{}: {}", part.name(), name);
+        }
+    }
+    assert_eq!(
+        names(Part::Extras),
+        ["ExtraSlot1", "ExtraSlot2", "JumpSlot"]
+    );
+}
+
+#[test]
+fn parse_part() {
+    for part in Part::MAPPINGS.into_iter().chain([
+        Part::Specials,
+        Part::GrindSpecials,
+        Part::Combo(id::AIR_SQUARE_U),
+    ]) {
+        assert_eq!(part.name().parse::<Part>().unwrap(), part);
+    }
+    assert!("grinds".parse::<Part>().is_err());
+}
@@ -0,0 +1,60 @@
+use cascade_save as save;
+mod common;
+
+fn read_bytes(bytes: &[u8], options: save::ReadOptions) -> save::Result<save::Save> {
+    save::Save::read_with(&mut &bytes[..], options)
+}
+
+#[test]
+fn saves_match_their_headers() {
+    for entry in common::entries() {
+        let result = save::Save::read(&mut entry.reader().unwrap());
+        assert!(
+            result.is_ok(),
+            "{} failed to verify: {:?}",
+            entry.filename(),
+            result.err()
+        );
+    }
+}
+
+#[test]
+fn mismatches_fail_strict_reads() {
+    let entry = &common::entries()[0];
+    let bytes = std::fs::read(entry.filepath()).expect("could not read save");
+
+    // checksum
+    let mut corrupted = bytes.clone();
+    corrupted[0] ^= 0xff;
+    assert!(matches!(
+        read_bytes(&corrupted, save::ReadOptions::strict()),
+        Err(save::Error::ChecksumMismatch {
+            field: "checksum",
+            ..
+        })
+    ));
+    assert!(read_bytes(&corrupted, save::ReadOptions::lenient()).is_ok());
+
+    // summary_checksum
+    let mut corrupted = bytes.clone();
+    corrupted[4] ^= 0xff;
+    assert!(matches!(
+        read_bytes(&corrupted, save::ReadOptions::strict()),
+        Err(save::Error::ChecksumMismatch {
+            field: "summary_checksum",
+            ..
+        })
+    ));
+
+    // total_size
+    let mut corrupted = bytes.clone();
+    corrupted[12] ^= 0x01;
+    assert!(matches!(
+        read_bytes(&corrupted, save::ReadOptions::strict()),
+        Err(save::Error::SizeMismatch {
+            field: "total_size",
+            ..
+        })
+    ));
+    assert!(read_bytes(&corrupted, save::ReadOptions::lenient()).is_ok());
+}